@@ -0,0 +1,158 @@
+use std::fmt;
+
+pub const REGISTER_NAMES: [&str; 2] = ["X", "Y"];
+pub const X: usize = 0;
+pub const Y: usize = 1;
+
+type Registers = [i32; REGISTER_NAMES.len()];
+
+/// Everything the CPU needs to know about an instruction
+pub struct InstructionSpec {
+    pub name: &'static str,
+    pub operands: usize,
+    pub cycles: usize,
+    /// Applied to the registers at the end of the last cycle of the instruction
+    pub effect: fn(&mut Registers, &[i32])
+}
+
+pub const INSTRUCTION_SET: [InstructionSpec; 3] = [
+    InstructionSpec { name: "noop", operands: 0, cycles: 1, effect: |_, _| {} },
+    InstructionSpec { name: "addx", operands: 1, cycles: 2, effect: |r, o| r[X] += o[0] },
+    InstructionSpec { name: "addy", operands: 1, cycles: 2, effect: |r, o| r[Y] += o[0] }
+];
+
+pub struct Instruction {
+    spec: &'static InstructionSpec,
+    operands: Vec<i32>
+}
+
+impl Instruction {
+    pub fn from(line: &str) -> Result<Self, ParseError> {
+        let mut parts = line.split(' ');
+        let name = parts.next().expect("Split yields at least one part");
+        let spec = INSTRUCTION_SET.iter()
+            .find(|spec| spec.name == name)
+            .ok_or_else(|| ParseError::UnknownOpcode(name.to_string()))?;
+
+        let mut operands: Vec<i32> = Vec::new();
+        for operand in parts {
+            let value = operand.parse()
+                .map_err(|_| ParseError::InvalidOperand(operand.to_string()))?;
+            operands.push(value);
+        }
+        if operands.len() != spec.operands {
+            return Err(ParseError::OperandCount(name.to_string(), spec.operands, operands.len()));
+        }
+
+        Ok(Instruction { spec, operands })
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.spec.name)?;
+        for operand in &self.operands {
+            write!(f, " {}", operand)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    UnknownOpcode(String),
+    InvalidOperand(String),
+    OperandCount(String, usize, usize)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownOpcode(name) => write!(f, "Unknown opcode {}", name),
+            ParseError::InvalidOperand(operand) => write!(f, "Operand {} is not a number", operand),
+            ParseError::OperandCount(name, expected, found) => {
+                write!(f, "{} expects {} operands, found {}", name, expected, found)
+            }
+        }
+    }
+}
+
+pub fn parse_program(lines: Vec<&str>) -> Result<Vec<Instruction>, (usize, ParseError)> {
+    lines.iter()
+        .enumerate()
+        .map(|(i, line)| Instruction::from(line).map_err(|e| (i + 1, e)))
+        .collect()
+}
+
+/// Gets called once during every cycle, before an instruction finishing in
+/// that cycle changes the registers
+pub trait CycleObserver {
+    fn on_cycle(&mut self, cpu: &Cpu);
+}
+
+pub struct Cpu {
+    program: Vec<Instruction>,
+    registers: Registers,
+    /// Number of the cycle which runs next, starting with 1
+    cycle: usize,
+    pc: usize,
+    /// Cycles the current instruction still needs, 0 if none is started
+    remaining: usize
+}
+
+impl Cpu {
+    pub fn new(program: Vec<Instruction>) -> Self {
+        let mut registers = [0; REGISTER_NAMES.len()];
+        registers[X] = 1;
+
+        Cpu { program, registers, cycle: 1, pc: 0, remaining: 0 }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Runs a single cycle, returns false if the program has already ended
+    pub fn tick(&mut self, observer: &mut dyn CycleObserver) -> bool {
+        if self.is_halted() {
+            return false;
+        }
+
+        let instruction = &self.program[self.pc];
+        if self.remaining == 0 {
+            self.remaining = instruction.spec.cycles;
+        }
+
+        observer.on_cycle(self);
+
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            (instruction.spec.effect)(&mut self.registers, &instruction.operands);
+            self.pc += 1;
+        }
+        self.cycle += 1;
+
+        true
+    }
+
+    pub fn run(&mut self, observer: &mut dyn CycleObserver) {
+        while self.tick(observer) {}
+    }
+
+    pub fn register(&self, register: usize) -> i32 {
+        self.registers[register]
+    }
+
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn current_instruction(&self) -> Option<&Instruction> {
+        self.program.get(self.pc)
+    }
+}
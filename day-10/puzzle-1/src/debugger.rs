@@ -0,0 +1,143 @@
+use std::io::{self, BufRead, Write};
+
+use crate::cpu::{Cpu, CycleObserver, REGISTER_NAMES, X};
+
+enum Breakpoint {
+    Cycle(usize),
+    /// Hit when the register changes to the value, not while it keeps it
+    Register(usize, i32)
+}
+
+impl Breakpoint {
+    fn is_hit(&self, before: &[i32], cpu: &Cpu) -> bool {
+        match self {
+            Breakpoint::Cycle(cycle) => cpu.cycle() == *cycle,
+            Breakpoint::Register(register, value) => {
+                before[*register] != *value && cpu.register(*register) == *value
+            }
+        }
+    }
+}
+
+fn register_index(name: &str) -> Option<usize> {
+    REGISTER_NAMES.iter().position(|r| r.eq_ignore_ascii_case(name))
+}
+
+fn registers(cpu: &Cpu) -> Vec<i32> {
+    (0..REGISTER_NAMES.len()).map(|r| cpu.register(r)).collect()
+}
+
+fn status(cpu: &Cpu) -> String {
+    let instruction = match cpu.current_instruction() {
+        Some(instruction) => instruction.to_string(),
+        None => "halted".to_string()
+    };
+
+    format!("{}, {}, {}, {}", cpu.cycle(), cpu.pc(), instruction, cpu.register(X))
+}
+
+/// Prints `cycle, pc, instruction, X` for every cycle and hands the cycle on
+pub struct Tracer<'a> {
+    observer: &'a mut dyn CycleObserver
+}
+
+impl<'a> Tracer<'a> {
+    pub fn new(observer: &'a mut dyn CycleObserver) -> Self {
+        println!("cycle, pc, instruction, X");
+
+        Tracer { observer }
+    }
+}
+
+impl CycleObserver for Tracer<'_> {
+    fn on_cycle(&mut self, cpu: &Cpu) {
+        println!("{}", status(cpu));
+        self.observer.on_cycle(cpu);
+    }
+}
+
+/// Interactive debugger reading its commands from stdin
+pub struct Debugger<'a> {
+    cpu: &'a mut Cpu,
+    observer: &'a mut dyn CycleObserver,
+    breakpoints: Vec<Breakpoint>
+}
+
+impl<'a> Debugger<'a> {
+    pub fn new(cpu: &'a mut Cpu, observer: &'a mut dyn CycleObserver) -> Self {
+        Debugger { cpu, observer, breakpoints: Vec::new() }
+    }
+
+    pub fn run(&mut self) {
+        println!("Commands: step [N], continue, break at cycle N, break when X == V (once X changes to V), print X, quit");
+        println!("{}", status(self.cpu));
+
+        let stdin = io::stdin();
+        loop {
+            print!("(cpu) ");
+            io::stdout().flush().expect("Could not flush stdout");
+
+            let mut line = String::new();
+            if stdin.lock().read_line(&mut line).expect("Could not read stdin") == 0 {
+                break;
+            }
+
+            let command: Vec<&str> = line.split_whitespace().collect();
+            match command.as_slice() {
+                [] => continue,
+                ["step"] => self.step(1),
+                ["step", n] => match n.parse() {
+                    Ok(n) => self.step(n),
+                    Err(_) => println!("{} is not a number", n)
+                },
+                ["continue"] => self.continue_to_breakpoint(),
+                ["break", "at", "cycle", n] => match n.parse() {
+                    Ok(cycle) => self.breakpoints.push(Breakpoint::Cycle(cycle)),
+                    Err(_) => println!("{} is not a number", n)
+                },
+                ["break", "when", register, "==", value] => {
+                    match (register_index(register), value.parse()) {
+                        (Some(register), Ok(value)) => self.breakpoints.push(Breakpoint::Register(register, value)),
+                        _ => println!("Unknown register {} or invalid value {}", register, value)
+                    }
+                },
+                ["print", register] => match register_index(register) {
+                    Some(index) => println!("{} = {}", REGISTER_NAMES[index], self.cpu.register(index)),
+                    None => println!("Unknown register {}", register)
+                },
+                ["quit"] => break,
+                _ => println!("Unknown command {}", line.trim())
+            }
+        }
+    }
+
+    fn step(&mut self, cycles: usize) {
+        for _ in 0..cycles {
+            if !self.cpu.tick(self.observer) {
+                break;
+            }
+        }
+        self.report();
+    }
+
+    fn continue_to_breakpoint(&mut self) {
+        loop {
+            let before = registers(self.cpu);
+            if !self.cpu.tick(self.observer) {
+                break;
+            }
+            if self.breakpoints.iter().any(|b| b.is_hit(&before, self.cpu)) {
+                println!("Breakpoint hit");
+                break;
+            }
+        }
+        self.report();
+    }
+
+    fn report(&self) {
+        if self.cpu.is_halted() {
+            println!("Program halted after cycle {}", self.cpu.cycle() - 1);
+        }
+        println!("{}", status(self.cpu));
+    }
+}
@@ -1,10 +1,13 @@
+mod cpu;
+mod debugger;
+
 use std::env;
 use std::fs;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Not enough command line arguments");
+    if args.len() < 2 {
+        panic!("Usage: {} <input> [--trace|--debug] [--start N] [--interval N] [--end N] [--cycles N,N,...]", args[0]);
     }
     
     let input = &args[1];
@@ -12,96 +15,90 @@ fn main() {
         .expect("Could not read file");
     let lines: Vec<&str> = lines.trim().split('\n').collect();
 
-    let mut ops: Vec<Op> = Vec::new();
-    for line in lines {
-        ops.push(Op::from(line));
+    let program = match cpu::parse_program(lines) {
+        Ok(program) => program,
+        Err((line, error)) => panic!("Line {}: {}", line, error)
+    };
+
+    let mut mode: Option<&str> = None;
+    let (mut start, mut interval, mut end) = (20, 40, None);
+    let mut cycles: Option<Vec<usize>> = None;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--trace" | "--debug" => mode = Some(option),
+            "--start" => start = options.next().expect("No start given").parse().expect("Start is not a number"),
+            "--interval" => interval = options.next().expect("No interval given").parse().expect("Interval is not a number"),
+            "--end" => end = Some(options.next().expect("No end given").parse().expect("End is not a number")),
+            "--cycles" => cycles = Some(options.next().expect("No cycles given")
+                .split(',')
+                .map(|c| c.parse().expect("Cycle is not a number"))
+                .collect()),
+            _ => panic!("Unknown option {}", option)
+        }
+    }
+    if interval == 0 {
+        panic!("Interval has to be positive");
     }
 
-    let mut cpu = Cpu::new();
-    for op in &ops {
-        cpu.execute(op);
+    let schedule = match cycles {
+        Some(cycles) => Schedule::List(cycles),
+        None => Schedule::Interval { start, interval, end }
+    };
+
+    let mut cpu = cpu::Cpu::new(program);
+    let mut signal = SignalStrength { schedule, samples: Vec::new() };
+    match mode {
+        None => cpu.run(&mut signal),
+        Some("--trace") => cpu.run(&mut debugger::Tracer::new(&mut signal)),
+        Some(_) => debugger::Debugger::new(&mut cpu, &mut signal).run()
     }
 
-    println!("Signal strength sum {}", cpu.sum_of_signal_strengths());
+    for (cycle, x) in &signal.samples {
+        println!("Cycle {}: X = {}, signal strength {}", cycle, x, *cycle as i32 * x);
+    }
+    println!("Signal strength sum {}", signal.sum_of_signal_strengths());
 }
 
-#[derive(Debug)]
-enum Op {
-    Add(i32),
-    Noop
+/// Cycles during which the signal strength is sampled
+enum Schedule {
+    /// Every `interval` cycles from `start` on, up to and including `end`
+    Interval { start: usize, interval: usize, end: Option<usize> },
+    List(Vec<usize>)
 }
 
-impl Op {
-    fn from(command: &str) -> Self {
-        let mut command= command.split(" ");
-        let op = command.nth(0).expect("No command found");
-
-        match op {
-            "addx" => {
-                let amount = command.nth(0).expect("No value passed");
-                let amount: i32 = amount.parse().expect("No i32 value passed");
-
-                Op::Add(amount)
+impl Schedule {
+    fn contains(&self, cycle: usize) -> bool {
+        match self {
+            Schedule::Interval { start, interval, end } => {
+                cycle >= *start
+                    && end.is_none_or(|end| cycle <= end)
+                    && (cycle - start).is_multiple_of(*interval)
             },
-            "noop" => Op::Noop,
-            _ => panic!("Unknown cpu command")
+            Schedule::List(cycles) => cycles.contains(&cycle)
         }
     }
 }
 
-#[derive(Debug)]
-struct Cpu {
-    register_x: i32,
-    clock_count: usize,
-    signal_strengths: Vec<i32>
+struct SignalStrength {
+    schedule: Schedule,
+    /// Sampled cycles with the value of X during that cycle
+    samples: Vec<(usize, i32)>
 }
 
-impl Cpu {
-    fn new() -> Self {
-        Cpu { register_x: 1, clock_count: 1, signal_strengths: Vec::new() }
-    }
-
-    fn execute(&mut self, op: &Op) {
-        match op {
-            Op::Noop => self.execute_noop(),
-            Op::Add(x) => self.execute_add(*x)
-        }
-    }
-
-    fn execute_add(&mut self, value: i32) {
-        self.track_strength();
-        self.tick_clock();
-        self.track_strength();
-        self.tick_clock();
-        self.register_x += value;
-    }
-
-    fn execute_noop(&mut self) {
-        self.track_strength();
-        self.tick_clock();
-    }
-
-    fn tick_clock(&mut self) {
-        self.clock_count += 1;
-    }
-
-    fn track_strength(&mut self) {
-        if self.clock_count < 20 {
-            return;
-        }
-
-        if self.clock_count == 20 || (self.clock_count - 20) % 40 == 0 {
-            self.signal_strengths.push(self.clock_count as i32 * self.register_x);
+impl cpu::CycleObserver for SignalStrength {
+    fn on_cycle(&mut self, cpu: &cpu::Cpu) {
+        let cycle = cpu.cycle();
+        if self.schedule.contains(cycle) {
+            self.samples.push((cycle, cpu.register(cpu::X)));
         }
     }
+}
 
+impl SignalStrength {
     fn sum_of_signal_strengths(&self) -> i32 {
-        let mut sum = 0;
-
-        for strength in &self.signal_strengths {
-            sum += strength;
-        }
-
-        sum
+        self.samples.iter()
+            .map(|(cycle, x)| *cycle as i32 * x)
+            .sum()
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+use std::fs;
+
+use crate::cpu::{Cpu, CycleObserver, X};
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+/// Every letter is followed by an empty column
+const GLYPH_SPACING: usize = GLYPH_WIDTH + 1;
+
+const GLYPHS: [(char, [&str; GLYPH_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"])
+];
+
+/// Screen the CPU draws on, one pixel per cycle, row by row
+pub struct Crt {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>
+}
+
+impl Crt {
+    pub fn new(width: usize, height: usize) -> Self {
+        Crt { width, height, pixels: vec![false; width * height] }
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Reads the capital letters shown on the screen, `?` for unknown glyphs.
+    /// Only screens which are exactly one glyph high can be read.
+    pub fn recognise(&self) -> Option<String> {
+        if self.height != GLYPH_HEIGHT {
+            return None;
+        }
+
+        let letters = (0..self.width / GLYPH_SPACING)
+            .map(|i| self.recognise_glyph(i * GLYPH_SPACING))
+            .collect();
+
+        Some(letters)
+    }
+
+    fn recognise_glyph(&self, left: usize) -> char {
+        let glyph = GLYPHS.iter().find(|(_, rows)| {
+            rows.iter().enumerate().all(|(y, row)| {
+                row.chars().enumerate().all(|(x, c)| (c == '#') == self.pixel(left + x, y))
+            })
+        });
+
+        match glyph {
+            Some((letter, _)) => *letter,
+            None => '?'
+        }
+    }
+
+    /// Writes the screen as plain PBM image
+    pub fn write_image(&self, file: &str) {
+        let mut image = format!("P1\n{} {}\n", self.width, self.height);
+        for y in 0..self.height {
+            let row: Vec<&str> = (0..self.width)
+                .map(|x| if self.pixel(x, y) { "1" } else { "0" })
+                .collect();
+            image.push_str(&row.join(" "));
+            image.push('\n');
+        }
+
+        fs::write(file, image).expect("Could not write image");
+    }
+}
+
+impl CycleObserver for Crt {
+    fn on_cycle(&mut self, cpu: &Cpu) {
+        let position = cpu.cycle() - 1;
+        if position >= self.pixels.len() {
+            return;
+        }
+
+        let column = (position % self.width) as i32;
+        let sprite = cpu.register(X);
+        self.pixels[position] = column >= sprite - 1 && column <= sprite + 1;
+    }
+}
+
+impl fmt::Display for Crt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.pixel(x, y) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,10 +1,14 @@
+mod cpu;
+mod crt;
+mod debugger;
+
 use std::env;
 use std::fs;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Not enough command line arguments");
+    if args.len() < 2 {
+        panic!("Usage: {} <input> [--trace|--debug] [--width N] [--height N] [--image FILE]", args[0]);
     }
     
     let input = &args[1];
@@ -12,92 +16,40 @@ fn main() {
         .expect("Could not read file");
     let lines: Vec<&str> = lines.trim().split('\n').collect();
 
-    let mut ops: Vec<Op> = Vec::new();
-    for line in lines {
-        ops.push(Op::from(line));
-    }
-
-    let mut cpu = Cpu::new();
-    for op in &ops {
-        cpu.execute(op);
-    }
-}
-
-#[derive(Debug)]
-enum Op {
-    Add(i32),
-    Noop
-}
-
-impl Op {
-    fn from(command: &str) -> Self {
-        let mut command= command.split(" ");
-        let op = command.nth(0).expect("No command found");
-
-        match op {
-            "addx" => {
-                let amount = command.nth(0).expect("No value passed");
-                let amount: i32 = amount.parse().expect("No i32 value passed");
-
-                Op::Add(amount)
-            },
-            "noop" => Op::Noop,
-            _ => panic!("Unknown cpu command")
+    let program = match cpu::parse_program(lines) {
+        Ok(program) => program,
+        Err((line, error)) => panic!("Line {}: {}", line, error)
+    };
+
+    let mut mode: Option<&str> = None;
+    let mut width = 40;
+    let mut height = 6;
+    let mut image: Option<&String> = None;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--trace" | "--debug" => mode = Some(option),
+            "--width" => width = options.next().expect("No width given").parse().expect("Width is not a number"),
+            "--height" => height = options.next().expect("No height given").parse().expect("Height is not a number"),
+            "--image" => image = Some(options.next().expect("No image file given")),
+            _ => panic!("Unknown option {}", option)
         }
     }
-}
 
-#[derive(Debug)]
-struct Cpu {
-    register_x: i32,
-    clock_count: usize
-}
-
-impl Cpu {
-    fn new() -> Self {
-        Cpu { register_x: 1, clock_count: 0 }
+    let mut cpu = cpu::Cpu::new(program);
+    let mut crt = crt::Crt::new(width, height);
+    match mode {
+        None => cpu.run(&mut crt),
+        Some("--trace") => cpu.run(&mut debugger::Tracer::new(&mut crt)),
+        Some(_) => debugger::Debugger::new(&mut cpu, &mut crt).run()
     }
 
-    fn execute(&mut self, op: &Op) {
-        match op {
-            Op::Noop => self.execute_noop(),
-            Op::Add(x) => self.execute_add(*x)
-        }
+    print!("{}", crt);
+    match crt.recognise() {
+        Some(letters) => println!("Letters on screen: {}", letters),
+        None => println!("Letters can only be read from a screen 6 pixels high")
     }
-
-    fn execute_add(&mut self, value: i32) {
-        self.draw_crt();
-        self.tick_clock();
-        self.draw_crt();
-        self.tick_clock();
-        self.register_x += value;
+    if let Some(image) = image {
+        crt.write_image(image);
     }
-
-    fn execute_noop(&mut self) {
-        self.draw_crt();
-        self.tick_clock();
-    }
-
-    fn tick_clock(&mut self) {
-        self.clock_count += 1;
-    }
-
-    fn draw_crt(&self) {
-        if self.clock_count % 40 == 0 {
-            println!();
-        }
-
-        self.draw_pixel();
-    }
-
-    fn draw_pixel(&self) {
-        let mut character = '.';
-        let count = (self.clock_count % 40) as i32;
-
-        if count >= (self.register_x - 1) && count <= (self.register_x + 1) {
-            character = '#';
-        }
-
-        print!("{character}");
-    }
-}
\ No newline at end of file
+}
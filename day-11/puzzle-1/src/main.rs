@@ -1,10 +1,8 @@
 mod monkey;
 
-use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::env;
 use std::fs;
-use std::rc::Rc;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -17,14 +15,14 @@ fn main() {
         .expect("Could not read file");
     let lines: Vec<&str> = lines.trim().split('\n').collect();
 
-    let jungle = Jungle::from(lines);
+    let mut jungle = Jungle::from(lines);
     for _ in 1..=20 {
         jungle.play_round();
     }
 
     let mut inspections: Vec<i32> = Vec::new();
     for monkey in &jungle.monkeys {
-        inspections.push(monkey.borrow_mut().get_inspections());
+        inspections.push(monkey.get_inspections());
     }
 
     inspections.sort();
@@ -37,40 +35,38 @@ fn main() {
 }
 #[derive(Debug)]
 pub struct Jungle {
-    monkeys: Vec<Rc<RefCell<monkey::Monkey>>>
+    monkeys: Vec<monkey::Monkey>
 }
 
 impl Jungle {
     fn from(lines: Vec<&str>) -> Self {
         let mut lines = VecDeque::from(lines);
 
-        let mut monkeys: Vec<Rc<RefCell<monkey::Monkey>>> = Vec::new();
-        while lines.len() > 0 {
+        let mut monkeys: Vec<monkey::Monkey> = Vec::new();
+        while !lines.is_empty() {
             // remove empty lines between monkey blocks
-            if lines.front().unwrap().len() == 0 {
+            if lines.front().unwrap().is_empty() {
                 lines.pop_front();
             }
 
             let monkey = monkey::Monkey::from(&mut lines);
-            monkeys.push(Rc::new(RefCell::new(monkey)));
+            monkeys.push(monkey);
         }
 
         Jungle { monkeys }
     }
 
-    fn play_round(&self) {
-        for monkey in &self.monkeys {
-            let mut from_monkey = monkey.borrow_mut();
-            let items = from_monkey.play_round();
+    /// Monkeys are addressed by index, so the thrown items are collected
+    /// first and handed over afterwards. A monkey may throw to itself.
+    fn play_round(&mut self) {
+        for i in 0..self.monkeys.len() {
+            let items = self.monkeys[i].play_round();
 
             for (next_monkey, item) in items {
-                let mut next_monkey= self.monkeys.get(next_monkey)
+                self.monkeys.get_mut(next_monkey)
                     .expect("Expect monkey")
-                    .borrow_mut();
-
-                next_monkey.add_item(item);
+                    .add_item(item);
             }
         }
     }
 }
-
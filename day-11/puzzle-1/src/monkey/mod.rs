@@ -40,7 +40,7 @@ impl Monkey {
     pub fn play_round(&mut self) -> Vec<(usize, i32)> {
         let mut items: Vec<(usize, i32)> = Vec::new();
 
-        while self.items.len() > 0 {
+        while !self.items.is_empty() {
             self.inspections += 1;
 
             let item = self.items.pop_front().expect("Item expected");
@@ -1,19 +1,32 @@
-#[derive(Debug)]
-enum Operand {
+#[derive(Debug, PartialEq)]
+enum Token {
     Number(i32),
-    Variable
+    Variable,
+    Operator(char),
+    OpenParenthesis,
+    CloseParenthesis
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum Operation {
     Plus,
-    Multiply
+    Minus,
+    Multiply,
+    Divide,
+    Remainder
+}
+
+#[derive(Debug)]
+enum Expression {
+    Number(i32),
+    Variable,
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, Operation, Box<Expression>)
 }
 
 #[derive(Debug)]
 pub struct MonkeyOperation {
-    operation: Operation,
-    right_hand: Operand
+    expression: Expression
 }
 
 impl MonkeyOperation {
@@ -22,39 +35,143 @@ impl MonkeyOperation {
         if !monkey_expression.starts_with(EXPRESSION_START) {
             panic!("Unexpected monkey expression")
         }
-        let monkey_expression = &monkey_expression[EXPRESSION_START.len()..monkey_expression.len()];
-        let mut expression_parts = monkey_expression.split(" ");
-        expression_parts.next().expect("No left hand side");
-        let operation = expression_parts.next().expect("No operation");
-        let rhs = expression_parts.next().expect("No right hand side");
-
-        let operation = match operation {
-            "+" => Operation::Plus,
-            "*" => Operation::Multiply,
-            _ => panic!("Unknown operation")
-        };
+        let monkey_expression = &monkey_expression[EXPRESSION_START.len()..];
 
-        let rhs = match rhs {
-            "old" => Operand::Variable,
-            x => {
-                let number: i32 = x.parse().expect("No number at lhs");
+        let tokens = tokenize(monkey_expression);
+        let mut parser = Parser { tokens, position: 0 };
+        let expression = parser.parse_sum();
+        if parser.position != parser.tokens.len() {
+            panic!("Unexpected token {:?} in monkey expression", parser.tokens[parser.position]);
+        }
 
-                Operand::Number(number)
+        MonkeyOperation { expression }
+    }
+
+    pub fn evaluate(&self, old: i32) -> i32 {
+        self.expression.evaluate(old)
+    }
+}
+
+impl Expression {
+    fn evaluate(&self, old: i32) -> i32 {
+        match self {
+            Expression::Number(x) => *x,
+            Expression::Variable => old,
+            Expression::Negate(e) => -e.evaluate(old),
+            Expression::Binary(lhs, operation, rhs) => {
+                let lhs = lhs.evaluate(old);
+                let rhs = rhs.evaluate(old);
+
+                match operation {
+                    Operation::Plus => lhs + rhs,
+                    Operation::Minus => lhs - rhs,
+                    Operation::Multiply => lhs * rhs,
+                    Operation::Divide => lhs.checked_div(rhs).expect("Division by zero in monkey expression"),
+                    Operation::Remainder => lhs.checked_rem(rhs).expect("Division by zero in monkey expression")
+                }
             }
-        };
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut chars = expression.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' => continue,
+            '+' | '-' | '*' | '/' | '%' => tokens.push(Token::Operator(c)),
+            '(' => tokens.push(Token::OpenParenthesis),
+            ')' => tokens.push(Token::CloseParenthesis),
+            '0'..='9' => {
+                let mut number = c.to_string();
+                while let Some(d) = chars.next_if(|d| d.is_ascii_digit()) {
+                    number.push(d);
+                }
 
-        MonkeyOperation { operation, right_hand: rhs }
+                tokens.push(Token::Number(number.parse().expect("Number too large")));
+            },
+            'a'..='z' => {
+                let mut word = c.to_string();
+                while let Some(d) = chars.next_if(|d| d.is_ascii_lowercase()) {
+                    word.push(d);
+                }
+                if word != "old" {
+                    panic!("Unknown variable {}", word);
+                }
+
+                tokens.push(Token::Variable);
+            },
+            _ => panic!("Unexpected character {} in monkey expression", c)
+        }
     }
 
-    pub fn evaluate(&self, old: i32) -> i32 {
-        let rhs = match self.right_hand {
-            Operand::Number(x) => x,
-            Operand::Variable => old
+    tokens
+}
+
+/// Recursive descent parser with the usual precedence: `*`, `/` and `%` bind
+/// stronger than `+` and `-`, all of them are left associative
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize
+}
+
+impl Parser {
+    fn next_operator(&mut self, operators: &[char]) -> Option<Operation> {
+        let operation = match self.tokens.get(self.position) {
+            Some(Token::Operator(c)) if operators.contains(c) => match c {
+                '+' => Operation::Plus,
+                '-' => Operation::Minus,
+                '*' => Operation::Multiply,
+                '/' => Operation::Divide,
+                _ => Operation::Remainder
+            },
+            _ => return None
         };
+        self.position += 1;
+
+        Some(operation)
+    }
+
+    fn parse_sum(&mut self) -> Expression {
+        let mut lhs = self.parse_product();
+        while let Some(operation) = self.next_operator(&['+', '-']) {
+            let rhs = self.parse_product();
+            lhs = Expression::Binary(Box::new(lhs), operation, Box::new(rhs));
+        }
+
+        lhs
+    }
+
+    fn parse_product(&mut self) -> Expression {
+        let mut lhs = self.parse_factor();
+        while let Some(operation) = self.next_operator(&['*', '/', '%']) {
+            let rhs = self.parse_factor();
+            lhs = Expression::Binary(Box::new(lhs), operation, Box::new(rhs));
+        }
 
-        match self.operation {
-            Operation::Plus => old + rhs,
-            Operation::Multiply => old * rhs
+        lhs
+    }
+
+    fn parse_factor(&mut self) -> Expression {
+        let token = self.tokens.get(self.position).expect("Unexpected end of monkey expression");
+        self.position += 1;
+
+        match token {
+            Token::Number(x) => Expression::Number(*x),
+            Token::Variable => Expression::Variable,
+            Token::Operator('-') => Expression::Negate(Box::new(self.parse_factor())),
+            Token::OpenParenthesis => {
+                let expression = self.parse_sum();
+                if self.tokens.get(self.position) != Some(&Token::CloseParenthesis) {
+                    panic!("Missing closing parenthesis in monkey expression");
+                }
+                self.position += 1;
+
+                expression
+            },
+            _ => panic!("Unexpected token {:?} in monkey expression", token)
         }
     }
-}
\ No newline at end of file
+}
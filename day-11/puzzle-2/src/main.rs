@@ -1,15 +1,13 @@
 mod monkey;
 
-use std::cell::RefCell;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::env;
 use std::fs;
-use std::rc::Rc;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Not enough command line arguments");
+    if args.len() < 2 {
+        panic!("Usage: {} <input> [--rounds N] [--relief N|none] [--modulus product|lcm|none, none with --relief, / or %] [--top N] [--trace] [--summary N] [--follow ITEM] [--fast-forward]", args[0]);
     }
     
     let input = &args[1];
@@ -17,64 +15,268 @@ fn main() {
         .expect("Could not read file");
     let lines: Vec<&str> = lines.trim().split('\n').collect();
 
-    let jungle = Jungle::from(lines);
+    let mut simulation = Simulation { rounds: 10000, relief: None, modulus: Modulus::Product, top: 2 };
+    let mut trace = false;
+    let mut fast_forward = false;
+    let mut summary_every: Option<usize> = None;
+    let mut follow: Option<usize> = None;
+    let mut modulus: Option<Modulus> = None;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        if option == "--trace" {
+            trace = true;
+            continue;
+        }
+        if option == "--fast-forward" {
+            fast_forward = true;
+            continue;
+        }
+
+        let value = options.next().expect("Option without value");
+        match option.as_str() {
+            "--rounds" => simulation.rounds = value.parse().expect("Rounds is not a number"),
+            "--relief" => simulation.relief = match value.as_str() {
+                "none" => None,
+                x => Some(x.parse().expect("Relief is not a number"))
+            },
+            "--modulus" => modulus = Some(Modulus::from(value)),
+            "--top" => simulation.top = value.parse().expect("Top is not a number"),
+            "--summary" => summary_every = Some(value.parse().expect("Summary interval is not a number")),
+            "--follow" => follow = Some(value.parse().expect("Item id is not a number")),
+            _ => panic!("Unknown option {}", option)
+        }
+    }
+    // floor division does not commute with the modulus, so relief needs the
+    // full worry levels
+    simulation.modulus = match (simulation.relief, modulus) {
+        (Some(_), None | Some(Modulus::None)) => Modulus::None,
+        (Some(_), Some(_)) => panic!("--relief cannot be combined with a modulus, use --modulus none"),
+        (None, modulus) => modulus.unwrap_or(Modulus::Product)
+    };
+    if fast_forward && (trace || summary_every.is_some() || follow.is_some()) {
+        panic!("--fast-forward skips rounds, it cannot be combined with --trace, --summary or --follow");
+    }
+    // without a modulus the worry levels keep growing and no state repeats
+    if fast_forward && (simulation.relief.is_some() || matches!(simulation.modulus, Modulus::None)) {
+        panic!("--fast-forward needs a modulus and no relief");
+    }
+
+    let mut jungle = Jungle::from(lines, &simulation);
 
-    for _ in 1..=10000 {
-        jungle.play_round();
+    let mut lineage: Vec<(usize, monkey::Inspection)> = Vec::new();
+    let rounds = if fast_forward { 0 } else { simulation.rounds };
+    for round in 1..=rounds {
+        let inspections = jungle.play_round();
+
+        if trace {
+            for i in &inspections {
+                println!(
+                    "Round {}: Monkey {} inspects item {} with worry level {}, now {}, thrown to monkey {}",
+                    round, i.monkey, i.item, i.worry_before, i.worry_after, i.target
+                );
+            }
+        }
+        if let Some(every) = summary_every {
+            if round == 1 || round % every == 0 {
+                jungle.print_summary(round);
+            }
+        }
+        if let Some(item) = follow {
+            lineage.extend(inspections.into_iter()
+                .filter(|i| i.item == item)
+                .map(|i| (round, i)));
+        }
     }
 
-    let mut inspections: Vec<i128> = Vec::new();
-    for monkey in &jungle.monkeys {
-        inspections.push(monkey.borrow_mut().get_inspections());
+    if let Some(item) = follow {
+        println!("Path of item {}:", item);
+        for (round, i) in &lineage {
+            println!("  Round {}: monkey {} -> monkey {} (worry level {})", round, i.monkey, i.target, i.worry_after);
+        }
     }
 
-    inspections.sort();
-    let last_number = inspections.pop().expect("Number expected");
-    let second_to_last_number = inspections.pop().expect("Number expected");
+    let mut inspections = if fast_forward {
+        jungle.fast_forward(simulation.rounds)
+    } else {
+        jungle.get_inspections()
+    };
 
-    let result = last_number * second_to_last_number;
+    inspections.sort();
+    if inspections.len() < simulation.top {
+        panic!("Only {} monkeys in the jungle", inspections.len());
+    }
+    let result: i128 = inspections.iter().rev().take(simulation.top).product();
 
     println!("Monkey result is {result}");
 }
+
+/// How worry levels are kept from growing without bounds
+#[derive(Debug)]
+pub enum Modulus {
+    /// Worry levels grow freely
+    None,
+    /// Reduce modulo the product of all test dividers
+    Product,
+    /// Reduce modulo the least common multiple of all test dividers
+    Lcm
+}
+
+impl Modulus {
+    fn from(modulus: &str) -> Self {
+        match modulus {
+            "none" => Modulus::None,
+            "product" => Modulus::Product,
+            "lcm" => Modulus::Lcm,
+            _ => panic!("Unknown modulus {}", modulus)
+        }
+    }
+
+    fn for_dividers(&self, dividers: &[i128]) -> Option<i128> {
+        match self {
+            Modulus::None => None,
+            Modulus::Product => Some(dividers.iter().product()),
+            Modulus::Lcm => Some(dividers.iter().fold(1, |acc, d| acc / gcd(acc, *d) * d))
+        }
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b)
+    }
+}
+
 #[derive(Debug)]
+pub struct Simulation {
+    rounds: usize,
+    relief: Option<i128>,
+    modulus: Modulus,
+    top: usize
+}
+
+/// All monkeys of the jungle, addressed by their id. Cloning a jungle only
+/// copies the item queues, so it is cheap to try out what-if runs.
+#[derive(Debug, Clone)]
 pub struct Jungle {
-    monkeys: Vec<Rc<RefCell<monkey::Monkey>>>,
-    divider_product: i128
+    monkeys: Vec<monkey::Monkey>,
+    relief: Option<i128>,
+    modulus: Option<i128>
 }
 
 impl Jungle {
-    fn from(lines: Vec<&str>) -> Self {
+    fn from(lines: Vec<&str>, simulation: &Simulation) -> Self {
         let mut lines = VecDeque::from(lines);
-        let mut divider = 1 as i128;
+        let mut dividers: Vec<i128> = Vec::new();
 
-        let mut monkeys: Vec<Rc<RefCell<monkey::Monkey>>> = Vec::new();
-        while lines.len() > 0 {
+        let mut item_count = 0;
+
+        let mut monkeys: Vec<monkey::Monkey> = Vec::new();
+        while !lines.is_empty() {
             // remove empty lines between monkey blocks
-            if lines.front().unwrap().len() == 0 {
+            if lines.front().unwrap().is_empty() {
                 lines.pop_front();
             }
 
-            let monkey = monkey::Monkey::from(&mut lines);
-            divider *= monkey.get_test_dividor();
-            monkeys.push(Rc::new(RefCell::new(monkey)));
+            let monkey = monkey::Monkey::from(&mut lines, item_count);
+            if monkey.get_id() != monkeys.len() {
+                panic!("Monkey {} is out of order", monkey.get_id());
+            }
+            item_count += monkey.item_count();
+            dividers.push(monkey.get_test_dividor());
+            monkeys.push(monkey);
+        }
+
+        for monkey in &monkeys {
+            for target in monkey.get_targets() {
+                if target >= monkeys.len() {
+                    panic!("Monkey {} throws to unknown monkey {}", monkey.get_id(), target);
+                }
+            }
+        }
+
+        let modulus = simulation.modulus.for_dividers(&dividers);
+        if modulus.is_some() {
+            if let Some(monkey) = monkeys.iter().find(|monkey| !monkey.is_modular()) {
+                panic!("Monkey {} uses / or %, which needs --modulus none", monkey.get_id());
+            }
         }
 
-        Jungle { monkeys, divider_product: divider }
+        Jungle { monkeys, relief: simulation.relief, modulus }
     }
 
-    fn play_round(&self) {
-        for monkey in &self.monkeys {
-            let mut from_monkey = monkey.borrow_mut();
-            let items = from_monkey.play_round(self.divider_product);
+    /// Every monkey first inspects all of its items and only afterwards the
+    /// items are handed over, so a monkey may also throw to itself. Those
+    /// items are inspected again in the next round.
+    fn play_round(&mut self) -> Vec<monkey::Inspection> {
+        let mut inspections: Vec<monkey::Inspection> = Vec::new();
 
-            for (next_monkey, item) in items {
-                let mut next_monkey= self.monkeys.get(next_monkey)
-                    .expect("Expect monkey")
-                    .borrow_mut();
+        for id in 0..self.monkeys.len() {
+            let items = self.monkeys[id].play_round(self.relief, self.modulus);
 
-                next_monkey.add_item(item);
+            for (inspection, item) in items {
+                self.monkeys[inspection.target].add_item(item);
+                inspections.push(inspection);
             }
         }
+
+        inspections
     }
-}
 
+    fn get_inspections(&self) -> Vec<i128> {
+        self.monkeys.iter()
+            .map(|monkey| monkey.get_inspections())
+            .collect()
+    }
+
+    /// Worry levels of all items, per monkey and in queue order
+    fn state(&self) -> Vec<Vec<i128>> {
+        self.monkeys.iter()
+            .map(|monkey| monkey.worry_levels())
+            .collect()
+    }
+
+    /// Inspection counts after the given number of rounds. Plays rounds until
+    /// the jungle is in a state it has been in before and extrapolates the
+    /// inspections of the remaining rounds from that cycle. The rounds are
+    /// played on a copy, the jungle itself stays untouched.
+    fn fast_forward(&self, rounds: usize) -> Vec<i128> {
+        let mut jungle = self.clone();
+        let mut seen: HashMap<Vec<Vec<i128>>, usize> = HashMap::new();
+        let mut history: Vec<Vec<i128>> = Vec::new();
+
+        for round in 0..rounds {
+            let state = jungle.state();
+            history.push(jungle.get_inspections());
+
+            if let Some(&cycle_start) = seen.get(&state) {
+                let cycle_length = round - cycle_start;
+                println!("Jungle repeats every {} rounds from round {} on", cycle_length, cycle_start);
+
+                let remaining = rounds - round;
+                let cycles = (remaining / cycle_length) as i128;
+                let rest = remaining % cycle_length;
+
+                return (0..jungle.monkeys.len()).map(|m| {
+                    let per_cycle = history[round][m] - history[cycle_start][m];
+                    let in_rest = history[cycle_start + rest][m] - history[cycle_start][m];
+
+                    history[round][m] + cycles * per_cycle + in_rest
+                }).collect();
+            }
+            seen.insert(state, round);
+
+            jungle.play_round();
+        }
+
+        jungle.get_inspections()
+    }
+
+    fn print_summary(&self, round: usize) {
+        println!("== After round {} ==", round);
+        for monkey in &self.monkeys {
+            println!("Monkey {} inspected items {} times.", monkey.get_id(), monkey.get_inspections());
+        }
+        println!();
+    }
+}
@@ -41,4 +41,8 @@ impl MonkeyTest {
     pub fn get_divider(&self) -> i128 {
         self.divider
     }
+
+    pub fn get_targets(&self) -> [usize; 2] {
+        [self.true_monkey_id, self.false_monkey_id]
+    }
 }
\ No newline at end of file
@@ -2,28 +2,50 @@ mod evaluation;
 mod operation;
 
 use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// An item keeps its id while its worry level changes, so it can be followed
+/// from monkey to monkey
+#[derive(Debug, Clone)]
+pub struct Item {
+    id: usize,
+    worry: i128
+}
 
+/// A single inspection of an item, including where it is thrown to
 #[derive(Debug)]
+pub struct Inspection {
+    pub monkey: usize,
+    pub item: usize,
+    pub worry_before: i128,
+    pub worry_after: i128,
+    pub target: usize
+}
+
+/// Operation and test never change, so clones of a monkey share them
+#[derive(Debug, Clone)]
 pub struct Monkey {
-    items: VecDeque<i128>,
+    id: usize,
+    items: VecDeque<Item>,
     inspections: i128,
-    operation: operation::MonkeyOperation,
-    test: evaluation::MonkeyTest
+    operation: Rc<operation::MonkeyOperation>,
+    test: Rc<evaluation::MonkeyTest>
 }
 
 impl Monkey {
-    pub fn from(lines: &mut VecDeque<&str>) -> Self {
+    pub fn from(lines: &mut VecDeque<&str>, first_item_id: usize) -> Self {
         let id_line = lines.pop_front().expect("No monkey initiator line").to_string();
-        if !id_line.starts_with("Monkey ") {
+        if !id_line.starts_with("Monkey ") || !id_line.ends_with(':') {
             panic!("Unexpected monkey initiator > '{}'", id_line);
         }
+        let id: usize = id_line["Monkey ".len()..id_line.len() - 1].parse().expect("Monkey id is not a number");
 
         let items_line = lines.pop_front().expect("Expect items line");
         let items_line = &items_line["  Starting items: ".len()..];
         let items = items_line.split(", ");
-        let items: VecDeque<i128> = items.map(|x| {
-            let item: i128 = x.parse().expect("No number in items line");
-            item
+        let items: VecDeque<Item> = items.enumerate().map(|(i, x)| {
+            let worry: i128 = x.parse().expect("No number in items line");
+            Item { id: first_item_id + i, worry }
         }).collect();
 
         let operation_line = lines.pop_front().expect("Expect operation line");
@@ -34,31 +56,55 @@ impl Monkey {
         let false_line = lines.pop_front().expect("Expect false line");
         let test = evaluation::MonkeyTest::from(divider_line, true_line, false_line);
 
-        Monkey { items, inspections: 0, operation, test }
+        Monkey { id, items, inspections: 0, operation: Rc::new(operation), test: Rc::new(test) }
     }
 
-    pub fn play_round(&mut self, divider: i128) -> Vec<(usize, i128)> {
-        let mut items: Vec<(usize, i128)> = Vec::new();
+    pub fn play_round(&mut self, relief: Option<i128>, modulus: Option<i128>) -> Vec<(Inspection, Item)> {
+        let mut items: Vec<(Inspection, Item)> = Vec::new();
 
-        while self.items.len() > 0 {
+        while !self.items.is_empty() {
             self.inspections += 1;
 
             let item = self.items.pop_front().expect("Item expected");
-            let mut item = self.operation.evaluate(item);
-            item %= divider;
-
-            let next_monkey = self.test.evaluate(item);
-
-            items.push((next_monkey, item));
+            let mut worry = self.operation.evaluate(item.worry);
+            if let Some(relief) = relief {
+                worry /= relief; // bored monkey
+            }
+            if let Some(modulus) = modulus {
+                worry %= modulus;
+            }
+
+            let next_monkey = self.test.evaluate(worry);
+
+            let inspection = Inspection {
+                monkey: self.id,
+                item: item.id,
+                worry_before: item.worry,
+                worry_after: worry,
+                target: next_monkey
+            };
+            items.push((inspection, Item { id: item.id, worry }));
         }
 
         items
     }
 
-    pub fn add_item(&mut self, item: i128) {
+    pub fn add_item(&mut self, item: Item) {
         self.items.push_back(item);
     }
 
+    pub fn worry_levels(&self) -> Vec<i128> {
+        self.items.iter().map(|item| item.worry).collect()
+    }
+
+    pub fn item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+
     pub fn get_inspections(&self) -> i128 {
         self.inspections
     }
@@ -66,4 +112,12 @@ impl Monkey {
     pub fn get_test_dividor(&self) -> i128 {
         self.test.get_divider()
     }
+
+    pub fn get_targets(&self) -> [usize; 2] {
+        self.test.get_targets()
+    }
+
+    pub fn is_modular(&self) -> bool {
+        self.operation.is_modular()
+    }
 }
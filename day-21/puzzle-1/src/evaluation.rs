@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::fmt;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::Zero;
+
+use crate::{Monkey, MonkeyCalculation, MonkeyOperation};
+
+/// How `MonkeyCalculation::Divide` treats quotients which are not whole numbers
+pub enum EvaluationMode {
+    /// Truncate towards zero, like plain integer division
+    Integer,
+    /// Fail if a division leaves a remainder
+    Strict,
+    /// Keep exact fractions
+    Rational
+}
+
+impl EvaluationMode {
+    pub fn from_str(mode: &str) -> Self {
+        match mode {
+            "integer" => EvaluationMode::Integer,
+            "strict" => EvaluationMode::Strict,
+            "rational" => EvaluationMode::Rational,
+            _ => panic!("Unknown evaluation mode {}", mode)
+        }
+    }
+
+    fn divide(&self, monkey: &str, v1: BigRational, v2: BigRational) -> Result<BigRational, EvaluationError> {
+        if v2.is_zero() {
+            return Err(EvaluationError::DivisionByZero(monkey.to_string()));
+        }
+        let quotient = v1 / v2;
+
+        match self {
+            EvaluationMode::Integer => Ok(quotient.trunc()),
+            EvaluationMode::Strict if !quotient.is_integer() => {
+                Err(EvaluationError::InexactDivision(monkey.to_string()))
+            },
+            EvaluationMode::Strict | EvaluationMode::Rational => Ok(quotient)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum EvaluationError {
+    UnknownMonkey(String),
+    DivisionByZero(String),
+    InexactDivision(String)
+}
+
+impl fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvaluationError::UnknownMonkey(name) => write!(f, "Unknown monkey name {}", name),
+            EvaluationError::DivisionByZero(name) => write!(f, "Monkey {} divides by zero", name),
+            EvaluationError::InexactDivision(name) => write!(f, "Monkey {} divides with a remainder", name)
+        }
+    }
+}
+
+pub fn value_for_monkey(for_name: &str, monkeys: &HashMap<String, Monkey>, mode: &EvaluationMode) -> Result<BigRational, EvaluationError> {
+    let m = monkeys.get(for_name)
+        .ok_or_else(|| EvaluationError::UnknownMonkey(for_name.to_string()))?;
+
+    match &m.operation {
+        MonkeyOperation::Number(x) => Ok(BigRational::from_integer(BigInt::from(*x))),
+        MonkeyOperation::Binary(m1, op, m2) => {
+            let v1 = value_for_monkey(m1, monkeys, mode)?;
+            let v2 = value_for_monkey(m2, monkeys, mode)?;
+
+            match op {
+                MonkeyCalculation::Plus => Ok(v1 + v2),
+                MonkeyCalculation::Minus => Ok(v1 - v2),
+                MonkeyCalculation::Multiply => Ok(v1 * v2),
+                MonkeyCalculation::Divide => mode.divide(for_name, v1, v2)
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Monkey, MonkeyOperation};
+use crate::evaluation::{self, EvaluationMode};
+
+/// Renders monkey expressions as formulas or as a Graphviz dependency graph
+pub struct ExpressionPrinter<'a> {
+    monkeys: &'a HashMap<String, Monkey>,
+    mode: &'a EvaluationMode,
+    variables: HashSet<String>,
+    simplify: bool
+}
+
+impl<'a> ExpressionPrinter<'a> {
+    pub fn new(monkeys: &'a HashMap<String, Monkey>, mode: &'a EvaluationMode) -> Self {
+        ExpressionPrinter { monkeys, mode, variables: HashSet::new(), simplify: false }
+    }
+
+    /// Keep the given monkey as a name in formulas instead of expanding it
+    pub fn keep_variable(&mut self, name: &str) {
+        self.variables.insert(name.to_string());
+    }
+
+    /// Replace subtrees without variables by their computed value
+    pub fn simplify(&mut self, simplify: bool) {
+        self.simplify = simplify;
+    }
+
+    pub fn formula(&self, for_name: &str) -> String {
+        format!("{} = {}", for_name, self.format_monkey(for_name, true))
+    }
+
+    fn format_monkey(&self, for_name: &str, expand: bool) -> String {
+        if !expand && self.variables.contains(for_name) {
+            return for_name.to_string();
+        }
+        let m = self.monkeys.get(for_name).expect("Unknown monkey name");
+
+        match &m.operation {
+            MonkeyOperation::Number(x) => x.to_string(),
+            MonkeyOperation::Binary(m1, op, m2) => {
+                if self.simplify && !self.depends_on_variable(for_name) {
+                    if let Ok(value) = evaluation::value_for_monkey(for_name, self.monkeys, self.mode) {
+                        return value.to_string();
+                    }
+                }
+
+                format!("({} {} {})", self.format_monkey(m1, false), op, self.format_monkey(m2, false))
+            }
+        }
+    }
+
+    fn depends_on_variable(&self, for_name: &str) -> bool {
+        if self.variables.contains(for_name) {
+            return true;
+        }
+
+        match &self.monkeys.get(for_name).expect("Unknown monkey name").operation {
+            MonkeyOperation::Number(_) => false,
+            MonkeyOperation::Binary(m1, _, m2) => {
+                self.depends_on_variable(m1) || self.depends_on_variable(m2)
+            }
+        }
+    }
+
+    /// Dependency graph of all monkeys in DOT format, every node labelled with
+    /// its name, operator and computed value
+    pub fn dot(&self) -> String {
+        let mut names: Vec<&String> = self.monkeys.keys().collect();
+        names.sort();
+
+        let mut dot = String::from("digraph monkeys {\n");
+        for name in names {
+            let value = match evaluation::value_for_monkey(name, self.monkeys, self.mode) {
+                Ok(value) => value.to_string(),
+                Err(_) => "?".to_string()
+            };
+
+            match &self.monkeys[name].operation {
+                MonkeyOperation::Number(_) => {
+                    dot.push_str(&format!("    \"{}\" [label=\"{}\\n{}\"];\n", name, name, value));
+                },
+                MonkeyOperation::Binary(m1, op, m2) => {
+                    dot.push_str(&format!("    \"{}\" [label=\"{}\\n{}\\n{}\"];\n", name, name, op, value));
+                    dot.push_str(&format!("    \"{}\" -> \"{}\";\n", name, m1));
+                    dot.push_str(&format!("    \"{}\" -> \"{}\";\n", name, m2));
+                }
+            }
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+}
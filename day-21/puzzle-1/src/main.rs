@@ -1,12 +1,18 @@
 use regex::Regex;
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs;
 
+mod evaluation;
+mod expression;
+use evaluation::EvaluationMode;
+use expression::ExpressionPrinter;
+
 fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Not enough command line arguments");
+    if args.len() < 2 {
+        panic!("Usage: {} <input> [--mode integer|strict|rational] [--print <monkey>] [--keep <monkey>] [--simplify] [--dot <file>]", args[0]);
     }
     
     let input = &args[1];
@@ -15,8 +21,41 @@ fn main() {
     let lines: Vec<&str> = lines.trim().split('\n').collect();
     let monkeys = parse_monkey_lines(lines);
 
-    let result = value_for_monkey(&"root".to_string(), &monkeys);
-    println!("Result for root is {}", result);
+    let mut mode = EvaluationMode::Integer;
+    let mut print: Option<&String> = None;
+    let mut keep: Vec<&String> = vec![];
+    let mut simplify = false;
+    let mut dot_file: Option<&String> = None;
+
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--mode" => mode = EvaluationMode::from_str(options.next().expect("No mode given")),
+            "--print" => print = Some(options.next().expect("No monkey to print given")),
+            "--keep" => keep.push(options.next().expect("No monkey to keep given")),
+            "--simplify" => simplify = true,
+            "--dot" => dot_file = Some(options.next().expect("No DOT file given")),
+            _ => panic!("Unknown option {}", option)
+        }
+    }
+
+    match evaluation::value_for_monkey("root", &monkeys, &mode) {
+        Ok(result) => println!("Result for root is {}", result),
+        Err(error) => println!("Could not compute root: {}", error)
+    }
+
+    let mut printer = ExpressionPrinter::new(&monkeys, &mode);
+    for name in keep {
+        printer.keep_variable(name);
+    }
+    printer.simplify(simplify);
+
+    if let Some(name) = print {
+        println!("{}", printer.formula(name));
+    }
+    if let Some(dot_file) = dot_file {
+        fs::write(dot_file, printer.dot()).expect("Could not write DOT file");
+    }
 }
 
 fn parse_monkey_lines(lines: Vec<&str>) -> HashMap<String, Monkey> {
@@ -38,25 +77,6 @@ fn parse_monkey_lines(lines: Vec<&str>) -> HashMap<String, Monkey> {
     monkeys
 }
 
-fn value_for_monkey(for_name: &String, monkeys: &HashMap<String, Monkey>) -> i128 {
-    let m = monkeys.get(for_name).expect("Unknown monkey name");
-
-    match &m.operation {
-        MonkeyOperation::Number(x) => x.clone(),
-        MonkeyOperation::Binary(m1, op, m2) => {
-            let v1 = value_for_monkey(m1, monkeys);
-            let v2 = value_for_monkey(m2, monkeys);
-
-            match op {
-                MonkeyCalculation::Plus => v1 + v2,
-                MonkeyCalculation::Minus => v1 - v2,
-                MonkeyCalculation::Multiply => v1 * v2,
-                MonkeyCalculation::Divide => v1 / v2
-            }
-        }
-    }
-}
-
 struct Monkey {
     operation: MonkeyOperation
 }
@@ -67,7 +87,7 @@ impl Monkey {
     }
 
     fn from_str(compute: &str) -> Self {
-        let mut parts = compute.split(" ");
+        let mut parts = compute.split(' ');
         let monkey1 = parts.next().expect("No first monkey in computation");
         let operation = match parts.next().expect("No monkey operation") {
             "+" => MonkeyCalculation::Plus,
@@ -93,3 +113,16 @@ enum MonkeyCalculation {
     Multiply,
     Divide
 }
+
+impl fmt::Display for MonkeyCalculation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            MonkeyCalculation::Plus => "+",
+            MonkeyCalculation::Minus => "-",
+            MonkeyCalculation::Multiply => "*",
+            MonkeyCalculation::Divide => "/"
+        };
+
+        write!(f, "{}", symbol)
+    }
+}
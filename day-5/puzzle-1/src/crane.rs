@@ -0,0 +1,96 @@
+use std::fmt;
+
+/// How many crates a crane lifts at once. Crates lifted together keep their
+/// order, crates lifted one after another end up reversed.
+#[derive(Debug)]
+pub enum Crane {
+    /// CrateMover 9000, one crate per lift
+    SingleCrate,
+    /// CrateMover 9001, all crates of a move in one lift
+    MultiCrate,
+    /// At most the given number of crates per lift
+    MaxCrates(usize)
+}
+
+impl Crane {
+    pub fn from(model: &str) -> Self {
+        match model {
+            "9000" => Crane::SingleCrate,
+            "9001" => Crane::MultiCrate,
+            x => match x.parse() {
+                Ok(0) | Err(_) => panic!("Unknown crane model {}", x),
+                Ok(max) => Crane::MaxCrates(max)
+            }
+        }
+    }
+
+    fn lift_size(&self, number_crates: usize) -> usize {
+        match self {
+            Crane::SingleCrate => 1,
+            Crane::MultiCrate => number_crates.max(1),
+            Crane::MaxCrates(max) => *max
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum MoveError {
+    UnknownStack(usize),
+    NotEnoughCrates { stack: usize, requested: usize, available: usize }
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::UnknownStack(stack) => write!(f, "There is no stack {}", stack),
+            MoveError::NotEnoughCrates { stack, requested, available } => {
+                write!(f, "Cannot take {} crates from stack {} holding {}", requested, stack, available)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MoveCommand {
+    number_crates: usize,
+    from_stack: usize,
+    to_stack: usize
+}
+
+impl MoveCommand {
+    pub fn new(input: &str) -> Self {
+        let input: Vec<&str> = input.split(' ').collect();
+        let number: usize = input[1].parse().expect("Number of crates is not a number");
+        let from: usize = input[3].parse().expect("Stack is not a number");
+        let to: usize = input[5].parse().expect("Stack is not a number");
+
+        MoveCommand { number_crates: number, from_stack: from, to_stack: to }
+    }
+
+    /// Stacks are numbered from 1, `stacks[0]` holds stack 1
+    pub fn execute(&self, stacks: &mut [Vec<String>], crane: &Crane) -> Result<(), MoveError> {
+        for stack in [self.from_stack, self.to_stack] {
+            if stack == 0 || stack > stacks.len() {
+                return Err(MoveError::UnknownStack(stack));
+            }
+        }
+
+        let available = stacks[self.from_stack - 1].len();
+        if available < self.number_crates {
+            return Err(MoveError::NotEnoughCrates { stack: self.from_stack, requested: self.number_crates, available });
+        }
+
+        let lift_size = crane.lift_size(self.number_crates);
+        let mut remaining = self.number_crates;
+        while remaining > 0 {
+            let lifted = remaining.min(lift_size);
+            let from = &mut stacks[self.from_stack - 1];
+            let crates = from.split_off(from.len() - lifted);
+
+            stacks[self.to_stack - 1].extend(crates);
+            remaining -= lifted;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+/// A label of the bottom row with the columns it covers
+struct Column {
+    id: usize,
+    start: usize,
+    end: usize
+}
+
+/// Reads the drawing above the move commands. The label row decides which
+/// stack a crate belongs to: every crate goes to the label closest to its
+/// centre, so neither crates nor labels need to be one character wide.
+pub fn parse(lines: &[&str]) -> Vec<Vec<String>> {
+    let drawing: Vec<&str> = lines.iter()
+        .take_while(|line| !line.trim().is_empty() && !line.starts_with("move"))
+        .copied()
+        .collect();
+    let (label_row, crate_rows) = match drawing.split_last() {
+        Some(split) => split,
+        None => return Vec::new()
+    };
+
+    let columns = columns(label_row);
+    for (i, column) in columns.iter().enumerate() {
+        if column.id != i + 1 {
+            panic!("Stack labels have to count up from 1, found {} at position {}", column.id, i + 1);
+        }
+    }
+
+    let mut stacks: Vec<Vec<String>> = vec![Vec::new(); columns.len()];
+    for row in crate_rows.iter().rev() {
+        for (start, end, label) in crates(row) {
+            let centre = start + end;
+            let column = columns.iter()
+                .min_by_key(|column| centre.abs_diff(column.start + column.end))
+                .expect("Crates without stack labels");
+            stacks[column.id - 1].push(label);
+        }
+    }
+
+    stacks
+}
+
+fn columns(label_row: &str) -> Vec<Column> {
+    let mut columns: Vec<Column> = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (index, character) in label_row.chars().chain([' ']).enumerate() {
+        match (character.is_whitespace(), start) {
+            (false, None) => start = Some(index),
+            (true, Some(first)) => {
+                let label: String = label_row.chars().skip(first).take(index - first).collect();
+                let id = label.parse().unwrap_or_else(|_| panic!("Stack label {} is not a number", label));
+                columns.push(Column { id, start: first, end: index });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    columns
+}
+
+/// Start, end and label of every `[label]` in a row
+fn crates(row: &str) -> Vec<(usize, usize, String)> {
+    let characters: Vec<char> = row.chars().collect();
+    let mut crates: Vec<(usize, usize, String)> = Vec::new();
+    let mut index = 0;
+
+    while index < characters.len() {
+        if characters[index] == '[' {
+            let length = characters[index..].iter()
+                .position(|c| *c == ']')
+                .unwrap_or_else(|| panic!("Unclosed crate in line '{}'", row));
+            let label: String = characters[index + 1..index + length].iter().collect();
+            if label.is_empty() {
+                panic!("Crate without label in line '{}'", row);
+            }
+
+            crates.push((index, index + length + 1, label));
+            index += length;
+        }
+        index += 1;
+    }
+
+    crates
+}
+
+/// Draws the stacks the way the puzzle input does, every column wide enough
+/// for its widest crate and label
+pub fn render(stacks: &[Vec<String>]) -> String {
+    let widest_crate = stacks.iter().flatten().map(|label| label.chars().count()).max().unwrap_or(1);
+    let widest_label = stacks.len().to_string().len();
+    let width = (widest_crate + 2).max(widest_label);
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+
+    let mut drawing = String::new();
+    for level in (0..height).rev() {
+        let cells: Vec<String> = stacks.iter()
+            .map(|stack| match stack.get(level) {
+                Some(label) => format!("{:^width$}", format!("[{}]", label), width = width),
+                None => " ".repeat(width)
+            })
+            .collect();
+        drawing.push_str(cells.join(" ").trim_end());
+        drawing.push('\n');
+    }
+
+    let labels: Vec<String> = (1..=stacks.len())
+        .map(|id| format!("{:^width$}", id, width = width))
+        .collect();
+    drawing.push_str(labels.join(" ").trim_end());
+    drawing.push('\n');
+
+    drawing
+}
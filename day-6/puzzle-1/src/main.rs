@@ -1,36 +1,46 @@
+mod marker;
+
 use std::env;
 use std::fs;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Not enough command line arguments");
+    if args.len() < 2 {
+        panic!("Usage: {} <input> [--packet N] [--message N] [--all] [--decode]", args[0]);
     }
     
     let input = &args[1];
-    let lines = fs::read_to_string(input)
-        .expect("Could not read file");
-    let lines: Vec<&str> = lines.trim().split('\n').collect();
-    let line = lines[0];
+    let mut packet_window = 4;
+    let mut message_window = 14;
+    let mut all = false;
+    let mut decode = false;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--packet" => packet_window = options.next().expect("No window given").parse().expect("Window is not a number"),
+            "--message" => message_window = options.next().expect("No window given").parse().expect("Window is not a number"),
+            "--all" => all = true,
+            "--decode" => decode = true,
+            _ => panic!("Unknown option {}", option)
+        }
+    }
 
-    let start = match find_start_sequence(line) {
-        None => panic!("No start sequence found"),
-        Some(x) => x
-    };
-    println!("Start sequence ends at character {}", start + 1);
-}
+    let file = fs::File::open(input).expect("Could not read file");
+    let datastreams = marker::scan_datastreams(file, packet_window, message_window, decode);
 
-fn find_start_sequence(input: &str) -> Option<usize> {
-    for i in 3..input.len() {
-        let first = input.get(i - 3..=i - 3).unwrap();
-        let second = input.get(i - 2..=i - 2).unwrap();
-        let third = input.get(i - 1..=i - 1).unwrap();
-        let fourth = input.get(i..=i).unwrap();
+    for (i, datastream) in datastreams.iter().enumerate() {
+        let packet = datastream.packet_markers.first()
+            .map_or(String::from("none"), |p| p.to_string());
+        let message = datastream.message_markers.first()
+            .map_or(String::from("none"), |m| m.to_string());
+        println!("Datastream {}: start-of-packet after character {}, start-of-message after character {}", i + 1, packet, message);
 
-        if first != second && second != third && third != fourth && first != third && first != fourth && second != fourth {
-            return Some(i);
+        if all {
+            println!("  All start-of-packet markers: {:?}", datastream.packet_markers);
+            println!("  All start-of-message markers: {:?}", datastream.message_markers);
+        }
+        if decode {
+            println!("  Message: {}", String::from_utf8_lossy(&datastream.message));
         }
     }
-
-    None
 }
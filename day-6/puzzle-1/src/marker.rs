@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+use std::io::{BufReader, Read};
+
+/// Sliding window over a datastream which knows at every byte whether the
+/// last `window` bytes are all different. Keeps a count per byte value, so
+/// every byte costs the same no matter how large the window is.
+pub struct MarkerDetector {
+    window: usize,
+    recent: VecDeque<u8>,
+    counts: [usize; 256],
+    duplicates: usize
+}
+
+impl MarkerDetector {
+    pub fn new(window: usize) -> Self {
+        if window == 0 {
+            panic!("Marker window has to hold at least one character");
+        }
+
+        MarkerDetector { window, recent: VecDeque::with_capacity(window + 1), counts: [0; 256], duplicates: 0 }
+    }
+
+    /// Feeds the next byte, true if it completes a marker
+    pub fn push(&mut self, byte: u8) -> bool {
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] > 1 {
+            self.duplicates += 1;
+        }
+        self.recent.push_back(byte);
+
+        if self.recent.len() > self.window {
+            let old = self.recent.pop_front().unwrap();
+            if self.counts[old as usize] > 1 {
+                self.duplicates -= 1;
+            }
+            self.counts[old as usize] -= 1;
+        }
+
+        self.recent.len() == self.window && self.duplicates == 0
+    }
+}
+
+/// Markers found in one line of the input
+pub struct Datastream {
+    pub packet_markers: Vec<usize>,
+    pub message_markers: Vec<usize>,
+    /// Everything after the first start-of-message marker, if requested
+    pub message: Vec<u8>
+}
+
+impl Datastream {
+    fn new() -> Self {
+        Datastream { packet_markers: Vec::new(), message_markers: Vec::new(), message: Vec::new() }
+    }
+}
+
+/// Reads the datastreams line by line without loading the input. Positions
+/// are the number of characters read from the line when a marker was complete.
+pub fn scan_datastreams<R: Read>(reader: R, packet_window: usize, message_window: usize, decode: bool) -> Vec<Datastream> {
+    let mut datastreams: Vec<Datastream> = Vec::new();
+    let mut current = Datastream::new();
+    let mut packet = MarkerDetector::new(packet_window);
+    let mut message = MarkerDetector::new(message_window);
+    let mut position = 0;
+
+    for byte in BufReader::new(reader).bytes() {
+        let byte = byte.expect("Could not read datastream");
+        if byte == b'\r' {
+            continue;
+        }
+        if byte == b'\n' {
+            if position > 0 {
+                datastreams.push(current);
+            }
+            current = Datastream::new();
+            packet = MarkerDetector::new(packet_window);
+            message = MarkerDetector::new(message_window);
+            position = 0;
+
+            continue;
+        }
+
+        if decode && !current.message_markers.is_empty() {
+            current.message.push(byte);
+        }
+
+        position += 1;
+        if packet.push(byte) {
+            current.packet_markers.push(position);
+        }
+        if message.push(byte) {
+            current.message_markers.push(position);
+        }
+    }
+    if position > 0 {
+        datastreams.push(current);
+    }
+
+    datastreams
+}
@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::Path;
+
+use crate::filesystem::{FileSystem, NodeId, ROOT};
+
+/// Nested JSON objects with name and size of every directory and file
+pub fn to_json(file_system: &FileSystem) -> String {
+    let mut json = String::new();
+    directory_json(file_system, ROOT, 0, &mut json);
+    json.push('\n');
+
+    json
+}
+
+fn directory_json(file_system: &FileSystem, id: NodeId, depth: usize, json: &mut String) {
+    let dir = file_system.get(id);
+    let indent = "  ".repeat(depth + 1);
+
+    json.push_str("{\n");
+    json.push_str(&format!("{}\"name\": {},\n", indent, quote(&dir.name)));
+    json.push_str(&format!("{}\"size\": {},\n", indent, file_system.get_size(id)));
+
+    json.push_str(&format!("{}\"files\": [", indent));
+    for (i, file) in dir.files.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        json.push_str(&format!("{}\n{}  {{\"name\": {}, \"size\": {}}}", separator, indent, quote(&file.name), file.size));
+    }
+    if !dir.files.is_empty() {
+        json.push_str(&format!("\n{}", indent));
+    }
+    json.push_str("],\n");
+
+    json.push_str(&format!("{}\"dirs\": [", indent));
+    for (i, child) in dir.dirs.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        json.push_str(&format!("{}\n{}  ", separator, indent));
+        directory_json(file_system, *child, depth + 1, json);
+    }
+    if !dir.dirs.is_empty() {
+        json.push_str(&format!("\n{}", indent));
+    }
+    json.push_str("]\n");
+
+    json.push_str(&format!("{}}}", "  ".repeat(depth)));
+}
+
+fn quote(name: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in name.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// Creates the tree below `target` with sparse files of the right sizes.
+/// Names which would leave the target directory are skipped.
+pub fn materialise(file_system: &FileSystem, target: &Path) {
+    materialise_directory(file_system, ROOT, target);
+}
+
+fn materialise_directory(file_system: &FileSystem, id: NodeId, path: &Path) {
+    fs::create_dir_all(path).expect("Could not create directory");
+
+    let dir = file_system.get(id);
+    for file in dir.files.iter().filter(|f| is_plain_name(&f.name)) {
+        let file_handle = fs::File::create(path.join(&file.name)).expect("Could not create file");
+        file_handle.set_len(file.size as u64).expect("Could not set file size");
+    }
+    for child in dir.dirs.iter().filter(|d| is_plain_name(&file_system.get(**d).name)) {
+        materialise_directory(file_system, *child, &path.join(&file_system.get(*child).name));
+    }
+}
+
+fn is_plain_name(name: &str) -> bool {
+    let plain = !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\']);
+    if !plain {
+        eprintln!("Warning: Skipped entry '{}'", name);
+    }
+
+    plain
+}
+
+/// Builds the model from a real directory, symbolic links are skipped
+pub fn scan(root: &Path) -> FileSystem {
+    let mut file_system = FileSystem::new();
+    scan_directory(&mut file_system, ROOT, root);
+    file_system.compute_sizes();
+
+    file_system
+}
+
+fn scan_directory(file_system: &mut FileSystem, id: NodeId, path: &Path) {
+    let mut entries: Vec<fs::DirEntry> = fs::read_dir(path)
+        .expect("Could not read directory")
+        .map(|entry| entry.expect("Could not read directory entry"))
+        .collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let metadata = fs::symlink_metadata(entry.path()).expect("Could not read metadata");
+
+        if metadata.is_dir() {
+            let child = file_system.add_dir(id, &name);
+            scan_directory(file_system, child, &entry.path());
+        } else if metadata.is_file() {
+            file_system.add_file(id, &name, metadata.len() as usize);
+        }
+    }
+}
@@ -1,180 +1,89 @@
-use std::cell::RefCell;
-use std::collections::VecDeque;
+mod export;
+mod filesystem;
+mod replay;
+mod report;
+
 use std::env;
-use std::fmt;
 use std::fs;
-use std::rc::Rc;
+use std::path::Path;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Not enough command line arguments");
+    if args.len() < 2 {
+        panic!("Usage: {} <input|directory> [--threshold N] [--json FILE] [--materialise DIR] [--tree] [--du] [--at-most N] [--freeing N] [path...]", args[0]);
     }
     
-    let input = &args[1];
-    let lines = fs::read_to_string(input)
-        .expect("Could not read file");
-    let lines: Vec<&str> = lines.trim().split('\n').collect();
-
-    let root_directory = parse_input(lines);
-    let mut small_directories: Vec<Rc<RefCell<Directory>>> = Vec::new();
-    find_small_directories(root_directory, &mut small_directories);
-
-    let mut size = 0;
-    for dir in small_directories {
-        let dir = dir.borrow();
-        size += dir.get_size();
-    }
-
-    println!("Size of small dirs {}", size);
-}
-
-fn find_small_directories(start: Rc<RefCell<Directory>>, found_directories: &mut Vec<Rc<RefCell<Directory>>>) {
-    if start.borrow().get_size() <= 100000 {
-        found_directories.push(Rc::clone(&start));
-
-    }
-
-    for dir in &start.borrow().dir_children {
-        find_small_directories(Rc::clone(&dir), found_directories);
-    }
-}
-
-
-fn handle_ls_command(remaining_lines: &mut VecDeque<&str>, cwd: Rc<RefCell<Directory>>) {
-    while remaining_lines.len() > 0 && !remaining_lines.front().unwrap().starts_with("$ ") {
-        let next_line = remaining_lines.pop_front().unwrap();
-        let mut mut_cwd = cwd.borrow_mut();
-        if next_line.starts_with("dir ") {
-            let dir_name: Vec<&str> = next_line.split(" ").collect();
-            let dir_name = dir_name.last().unwrap();
-
-            mut_cwd.add_dir(Rc::new(RefCell::new(Directory::new(dir_name, Rc::clone(&cwd)))));
-        } else { // this is a file
-            let file: Vec<&str> = next_line.split(" ").collect();
-            let size: usize = file.first().unwrap().parse().unwrap();
-
-            mut_cwd.add_file(Rc::new(RefCell::new(File::new(size))));
+    let input = Path::new(&args[1]);
+    let file_system = if input.is_dir() {
+        export::scan(input)
+    } else {
+        let lines = fs::read_to_string(input)
+            .expect("Could not read file");
+        let lines: Vec<&str> = lines.trim().split('\n').collect();
+
+        let (file_system, warnings) = replay::replay(&lines);
+        for warning in warnings {
+            eprintln!("Warning: {}", warning);
         }
-    }
-}
 
-fn parse_input(lines: Vec<&str>) -> Rc<RefCell<Directory>> {
-    if !lines[0..=0].first().unwrap().starts_with("$ cd /") {
-        panic!("Series of commands does not start with changing to root directory");
+        file_system
+    };
+    let mut threshold = 100000;
+    let mut reports: Vec<&String> = Vec::new();
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--threshold" => threshold = options.next().expect("No threshold given").parse().expect("Threshold is not a number"),
+            _ => reports.push(option)
+        }
     }
 
-    let mut lines_queue = VecDeque::from(lines);
-
-    let root = Rc::new(RefCell::new(Directory::new_root("/")));
-    let mut cwd = Rc::clone(&root);
+    let size: usize = file_system.directory_ids()
+        .map(|dir| file_system.get_size(dir))
+        .filter(|size| *size <= threshold)
+        .sum();
 
-    match lines_queue.pop_front() {
-        None => panic!("No elements at all"),
-        _ => ()
-    }
-
-    while !lines_queue.is_empty() {
-        let line = lines_queue.pop_front().unwrap();
-        if line.eq("$ ls") {
-            handle_ls_command(&mut lines_queue, Rc::clone(&cwd));
-        } else if line.starts_with("$ cd") {
-            let command: Vec<&str> = line.split(" ").collect();
-            let dir = command.last().unwrap();
+    println!("Size of small dirs {}", size);
 
-            let dir = match *dir {
-                ".." => match &cwd.borrow_mut().parent {
-                    None => Rc::clone(&root),
-                    Some(x) => Rc::clone(&x)
+    let mut options = reports.into_iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--tree" => print!("{}", report::tree(&file_system)),
+            "--json" => {
+                let file = options.next().expect("No JSON file given");
+                fs::write(file, export::to_json(&file_system)).expect("Could not write JSON file");
+            },
+            "--materialise" => {
+                let target = options.next().expect("No target directory given");
+                export::materialise(&file_system, Path::new(target));
+            },
+            "--du" => print_sizes(&report::du(&file_system)),
+            "--at-most" => {
+                let limit = options.next().expect("No limit given").parse().expect("Limit is not a number");
+                print_sizes(&report::at_most(&file_system, limit));
+            },
+            "--freeing" => {
+                let needed = options.next().expect("No size given").parse().expect("Size is not a number");
+                match report::smallest_freeing(&file_system, needed) {
+                    Some(dir) => print_sizes(&[dir]),
+                    None => println!("No directory frees {}", needed)
+                }
+            },
+            path => {
+                if let Some(dir) = file_system.lookup(path) {
+                    println!("Size of {} is {}", file_system.path(dir), file_system.get_size(dir));
+                } else if let Some(file) = file_system.lookup_file(path) {
+                    println!("Size of {} is {}", path, file.size);
+                } else {
+                    println!("No such file or directory {}", path);
                 }
-                _ =>  cwd.borrow_mut().get_dir_by_name(dir).unwrap()
-            };
-            cwd = Rc::clone(&dir);
+            }
         }
     }
-
-    root
-}
-
-struct Directory {
-    parent: Option<Rc<RefCell<Directory>>>,
-    dir_children: Vec<Rc<RefCell<Directory>>>,
-    file_children: Vec<Rc<RefCell<File>>>,
-    name: String
 }
 
-impl fmt::Debug for Directory {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Directory")
-            .field("dir_children", &self.dir_children.len())
-            .field("file_children", &self.file_children.len())
-            .field("name", &self.name)
-            .finish()
+fn print_sizes(sizes: &[(String, usize)]) {
+    for (path, size) in sizes {
+        println!("{}\t{}", size, path);
     }
 }
-
-impl Directory {
-    fn new(name: &str, parent: Rc<RefCell<Directory>>) -> Self {
-        Directory {
-            parent: Some(parent),
-            dir_children: Vec::new(),
-            file_children: Vec::new(),
-            name: String::from(name)
-        }
-    }
-
-    fn new_root(name: &str) -> Self {
-        Directory {
-            parent: None,
-            dir_children: Vec::new(),
-            file_children: Vec::new(),
-            name: String::from(name)
-        }
-    }
-
-    fn add_file(&mut self, child: Rc<RefCell<File>>) {
-        self.file_children.push(child);
-    }
-
-    fn add_dir(&mut self, child: Rc<RefCell<Directory>>) {
-        self.dir_children.push(child);
-    }
-
-    fn get_dir_by_name(&mut self, name: &str) -> Option<Rc<RefCell<Directory>>> {
-        match self.dir_children.iter().find(|d| d.borrow().name == name) {
-            None => None,
-            Some(x) => Some(x.clone())
-        }
-    }
-
-    fn get_size(&self) -> usize {
-        let mut size: usize = 0;
-
-        for child in &self.file_children {
-            let child = child.borrow();
-            size += child.get_size();
-        }
-
-        for child in &self.dir_children {
-            let child = child.borrow();
-            size += child.get_size();
-        }
-
-        size
-    }
-}
-
-#[derive(Debug)]
-struct File {
-    size: usize
-}
-
-impl File {
-    fn new(size: usize) -> Self {
-        File { size }
-    }
-
-    fn get_size(&self) -> usize {
-        self.size
-    }
-}
\ No newline at end of file
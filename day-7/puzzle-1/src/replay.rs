@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use crate::filesystem::{self, FileSystem, NodeId};
+
+/// Rebuilds the filesystem from a terminal session. Directories are created
+/// on demand, repeated listings are merged and everything which does not add
+/// up is reported as warning instead of aborting.
+struct Replay {
+    fs: FileSystem,
+    cwd: NodeId,
+    listed: HashSet<NodeId>,
+    warnings: Vec<String>
+}
+
+pub fn replay(lines: &[&str]) -> (FileSystem, Vec<String>) {
+    let mut replay = Replay {
+        fs: FileSystem::new(),
+        cwd: filesystem::ROOT,
+        listed: HashSet::new(),
+        warnings: Vec::new()
+    };
+
+    let mut lines = lines.iter().enumerate().peekable();
+    while let Some((number, line)) = lines.next() {
+        if *line == "$ ls" {
+            let mut entries: Vec<&str> = Vec::new();
+            while let Some((_, entry)) = lines.next_if(|(_, l)| !l.starts_with("$ ")) {
+                entries.push(entry);
+            }
+            replay.list(&entries);
+        } else if let Some(path) = line.strip_prefix("$ cd ") {
+            replay.cd(path);
+        } else {
+            replay.warnings.push(format!("Line {}: Unexpected line '{}'", number + 1, line));
+        }
+    }
+    replay.fs.compute_sizes();
+
+    (replay.fs, replay.warnings)
+}
+
+impl Replay {
+    fn cd(&mut self, path: &str) {
+        let mut dir = if path.starts_with('/') { filesystem::ROOT } else { self.cwd };
+
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            dir = match segment {
+                "." => dir,
+                ".." => self.fs.get(dir).parent.unwrap_or(filesystem::ROOT),
+                name => match self.fs.get_dir_by_name(dir, name) {
+                    Some(child) => child,
+                    None => {
+                        let child = self.fs.add_dir(dir, name);
+                        self.warnings.push(format!("Changed into unknown directory {}, created it", self.fs.path(child)));
+
+                        child
+                    }
+                }
+            };
+        }
+
+        self.cwd = dir;
+    }
+
+    fn list(&mut self, entries: &[&str]) {
+        let cwd = self.cwd;
+        let repeated = !self.listed.insert(cwd);
+        let mut names: HashSet<&str> = HashSet::new();
+
+        for entry in entries {
+            let (size_or_dir, name) = match entry.split_once(' ') {
+                Some(parts) => parts,
+                None => {
+                    self.warnings.push(format!("Listing of {} has invalid entry '{}'", self.fs.path(cwd), entry));
+                    continue;
+                }
+            };
+            names.insert(name);
+
+            let existing_dir = self.fs.get_dir_by_name(cwd, name);
+            let existing_file = self.fs.get(cwd).files.iter().find(|f| f.name == name).map(|f| f.size);
+
+            if size_or_dir == "dir" {
+                match (existing_dir, existing_file) {
+                    (Some(_), _) => (),
+                    (None, Some(_)) => self.warn(cwd, name, "is listed as file and directory"),
+                    (None, None) => {
+                        if repeated {
+                            self.warn(cwd, name, "appeared in a repeated listing");
+                        }
+                        self.fs.add_dir(cwd, name);
+                    }
+                }
+            } else {
+                let size: usize = match size_or_dir.parse() {
+                    Ok(size) => size,
+                    Err(_) => {
+                        self.warn(cwd, name, "has no valid size");
+                        continue;
+                    }
+                };
+
+                match (existing_dir, existing_file) {
+                    (Some(_), _) => self.warn(cwd, name, "is listed as file and directory"),
+                    (None, Some(existing_size)) if existing_size != size => {
+                        self.warn(cwd, name, &format!("changed its size from {} to {}, keeping the first", existing_size, size));
+                    },
+                    (None, Some(_)) => (),
+                    (None, None) => {
+                        if repeated {
+                            self.warn(cwd, name, "appeared in a repeated listing");
+                        }
+                        self.fs.add_file(cwd, name, size);
+                    }
+                }
+            }
+        }
+
+        // compared on every listing, directories created by cd may be missing
+        // in the first one already
+        let dir = self.fs.get(cwd);
+        let missing: Vec<String> = dir.dirs.iter()
+            .map(|d| self.fs.get(*d).name.clone())
+            .chain(dir.files.iter().map(|f| f.name.clone()))
+            .filter(|name| !names.contains(name.as_str()))
+            .collect();
+
+        let listing = if repeated { "a repeated listing" } else { "the first listing" };
+        for name in missing {
+            self.warn(cwd, &name, &format!("is missing in {}", listing));
+        }
+    }
+
+    fn warn(&mut self, dir: NodeId, name: &str, problem: &str) {
+        let path = match dir {
+            filesystem::ROOT => format!("/{}", name),
+            _ => format!("{}/{}", self.fs.path(dir), name)
+        };
+
+        self.warnings.push(format!("{} {}", path, problem));
+    }
+}
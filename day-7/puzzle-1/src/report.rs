@@ -0,0 +1,50 @@
+use crate::filesystem::{FileSystem, NodeId, ROOT};
+
+/// Whole tree in the format of the puzzle, entries sorted by name
+pub fn tree(fs: &FileSystem) -> String {
+    let mut output = String::new();
+    tree_entry(fs, ROOT, 0, &mut output);
+
+    output
+}
+
+fn tree_entry(fs: &FileSystem, id: NodeId, depth: usize, output: &mut String) {
+    let dir = fs.get(id);
+    output.push_str(&format!("{}- {} (dir)\n", "  ".repeat(depth), dir.name));
+
+    let mut children: Vec<(&str, Option<NodeId>, usize)> = dir.dirs.iter()
+        .map(|d| (fs.get(*d).name.as_str(), Some(*d), 0))
+        .chain(dir.files.iter().map(|f| (f.name.as_str(), None, f.size)))
+        .collect();
+    children.sort();
+
+    for (name, child_dir, size) in children {
+        match child_dir {
+            Some(child_dir) => tree_entry(fs, child_dir, depth + 1, output),
+            None => output.push_str(&format!("{}- {} (file, size={})\n", "  ".repeat(depth + 1), name, size))
+        }
+    }
+}
+
+/// Path and total size of every directory, largest first
+pub fn du(fs: &FileSystem) -> Vec<(String, usize)> {
+    let mut sizes: Vec<(String, usize)> = fs.directory_ids()
+        .map(|dir| (fs.path(dir), fs.get_size(dir)))
+        .collect();
+    sizes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    sizes
+}
+
+pub fn at_most(fs: &FileSystem, limit: usize) -> Vec<(String, usize)> {
+    du(fs).into_iter()
+        .filter(|(_, size)| *size <= limit)
+        .collect()
+}
+
+/// Smallest directory whose deletion frees at least `needed`
+pub fn smallest_freeing(fs: &FileSystem, needed: usize) -> Option<(String, usize)> {
+    du(fs).into_iter()
+        .filter(|(_, size)| *size >= needed)
+        .min_by_key(|(_, size)| *size)
+}
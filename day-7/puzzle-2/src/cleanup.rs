@@ -0,0 +1,118 @@
+use crate::filesystem::{FileSystem, NodeId, ROOT};
+
+/// Exact sizes below the needed space a set of deleted directories can add
+/// up to, one bit per size
+#[derive(Clone)]
+struct Sums {
+    needed: usize,
+    below: Vec<u64>
+}
+
+impl Sums {
+    fn new(needed: usize) -> Self {
+        Sums { needed, below: vec![0; needed.div_ceil(64)] }
+    }
+
+    /// Smallest sum which is at least `from`
+    fn first_from(&self, from: usize) -> Option<usize> {
+        if from >= self.needed {
+            return None;
+        }
+
+        let first_word = self.below[from / 64] & (u64::MAX << (from % 64));
+        let (w, word) = std::iter::once((from / 64, first_word))
+            .chain(self.below.iter().copied().enumerate().skip(from / 64 + 1))
+            .find(|(_, word)| *word != 0)?;
+
+        Some(w * 64 + word.trailing_zeros() as usize)
+    }
+
+    /// Adds all sums of `before` increased by `size`. Every sum which was not
+    /// reachable yet remembers `id` as the directory which made it reachable.
+    fn add_shifted(&mut self, before: &Sums, size: usize, id: u32, producers: &mut [u32]) {
+        let (word_shift, bit_shift) = (size / 64, size % 64);
+
+        for w in word_shift..self.below.len() {
+            let mut shifted = before.below[w - word_shift] << bit_shift;
+            if bit_shift > 0 && w > word_shift {
+                shifted |= before.below[w - word_shift - 1] >> (64 - bit_shift);
+            }
+            // bits past the needed space do not belong to the exact sums
+            if w == self.needed / 64 {
+                shifted &= (1 << (self.needed % 64)) - 1;
+            }
+
+            let mut new = shifted & !self.below[w];
+            while new != 0 {
+                producers[w * 64 + new.trailing_zeros() as usize] = id;
+                new &= new - 1;
+            }
+            self.below[w] |= shifted;
+        }
+    }
+}
+
+struct Search<'a> {
+    fs: &'a FileSystem,
+    needed: usize,
+    /// Directory which first made a sum reachable, 0 for the empty set
+    producers: Vec<u32>,
+    /// Smallest total of at least `needed`, the last directory and the rest
+    best: Option<(usize, NodeId, usize)>
+}
+
+impl Search<'_> {
+    /// Turns the sums reachable before the directory into the sums reachable
+    /// after its subtree, by deleting either the directory or some of the
+    /// directories inside it
+    fn visit(&mut self, id: NodeId, open: &mut Sums) {
+        let before = open.clone();
+        for child in &self.fs.get(id).dirs {
+            self.visit(*child, open);
+        }
+
+        let size = self.fs.get_size(id);
+        if let Some(rest) = before.first_from(self.needed.saturating_sub(size)) {
+            if self.best.is_none_or(|(total, _, _)| rest + size < total) {
+                self.best = Some((rest + size, id, rest));
+            }
+        }
+
+        let producer = u32::try_from(id).expect("Too many directories");
+        open.add_shifted(&before, size, producer, &mut self.producers);
+    }
+}
+
+/// Directories besides the root which together free at least `needed` while
+/// deleting as little as possible. No directory of the set contains another
+/// one, otherwise its size would be counted twice.
+///
+/// Works like a knapsack over the directories in pre-order. Only one set of
+/// sums per directory level is kept, the chosen directories are found again
+/// through the directory which first reached each sum, so memory grows with
+/// `needed` but not with the number of directories.
+pub fn minimal_deletion_set(fs: &FileSystem, needed: usize) -> Option<(Vec<NodeId>, usize)> {
+    if needed == 0 {
+        return Some((Vec::new(), 0));
+    }
+
+    let mut search = Search { fs, needed, producers: vec![0; needed], best: None };
+    let mut open = Sums::new(needed);
+    open.below[0] = 1;
+    for child in &fs.get(ROOT).dirs {
+        search.visit(*child, &mut open);
+    }
+
+    // a sum reached by a directory was reachable before that directory's
+    // subtree, so the directories found here never contain each other
+    let (total, last, mut remaining) = search.best?;
+    let mut set: Vec<NodeId> = vec![last];
+    while remaining > 0 {
+        let id = search.producers[remaining] as NodeId;
+        set.push(id);
+        remaining -= fs.get_size(id);
+    }
+    set.reverse();
+
+    Some((set, total))
+}
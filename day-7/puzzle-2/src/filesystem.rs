@@ -0,0 +1,108 @@
+pub type NodeId = usize;
+
+pub const ROOT: NodeId = 0;
+
+#[derive(Debug)]
+pub struct File {
+    pub name: String,
+    pub size: usize
+}
+
+#[derive(Debug)]
+pub struct Directory {
+    pub name: String,
+    pub parent: Option<NodeId>,
+    pub dirs: Vec<NodeId>,
+    pub files: Vec<File>,
+    /// Total size including all subdirectories, see `FileSystem::compute_sizes`
+    size: usize
+}
+
+/// All directories live in one arena and refer to each other by their id.
+/// A directory is always created after its parent, so its id is larger.
+#[derive(Debug)]
+pub struct FileSystem {
+    directories: Vec<Directory>
+}
+
+impl FileSystem {
+    pub fn new() -> Self {
+        let root = Directory { name: String::from("/"), parent: None, dirs: Vec::new(), files: Vec::new(), size: 0 };
+
+        FileSystem { directories: vec![root] }
+    }
+
+    pub fn add_dir(&mut self, parent: NodeId, name: &str) -> NodeId {
+        let id = self.directories.len();
+        self.directories.push(Directory {
+            name: String::from(name),
+            parent: Some(parent),
+            dirs: Vec::new(),
+            files: Vec::new(),
+            size: 0
+        });
+        self.directories[parent].dirs.push(id);
+
+        id
+    }
+
+    pub fn add_file(&mut self, parent: NodeId, name: &str, size: usize) {
+        self.directories[parent].files.push(File { name: String::from(name), size });
+    }
+
+    pub fn get(&self, id: NodeId) -> &Directory {
+        &self.directories[id]
+    }
+
+    pub fn get_dir_by_name(&self, parent: NodeId, name: &str) -> Option<NodeId> {
+        self.directories[parent].dirs.iter()
+            .copied()
+            .find(|d| self.directories[*d].name == name)
+    }
+
+    /// Sums up the sizes of all directories once, children before parents
+    pub fn compute_sizes(&mut self) {
+        for id in (0..self.directories.len()).rev() {
+            let dir = &self.directories[id];
+            let size = dir.files.iter().map(|f| f.size).sum::<usize>()
+                + dir.dirs.iter().map(|d| self.directories[*d].size).sum::<usize>();
+
+            self.directories[id].size = size;
+        }
+    }
+
+    pub fn get_size(&self, id: NodeId) -> usize {
+        self.directories[id].size
+    }
+
+    pub fn directory_ids(&self) -> impl Iterator<Item = NodeId> {
+        0..self.directories.len()
+    }
+
+    pub fn path(&self, id: NodeId) -> String {
+        match self.directories[id].parent {
+            None => String::from("/"),
+            Some(ROOT) => format!("/{}", self.directories[id].name),
+            Some(parent) => format!("{}/{}", self.path(parent), self.directories[id].name)
+        }
+    }
+
+    /// Finds a directory by its absolute path like `/a/e`
+    pub fn lookup(&self, path: &str) -> Option<NodeId> {
+        if !path.starts_with('/') {
+            return None;
+        }
+
+        path.split('/')
+            .filter(|segment| !segment.is_empty())
+            .try_fold(ROOT, |dir, segment| self.get_dir_by_name(dir, segment))
+    }
+
+    /// Finds a file by its absolute path like `/a/e/i`
+    pub fn lookup_file(&self, path: &str) -> Option<&File> {
+        let (dir, name) = path.rsplit_once('/')?;
+        let dir = if dir.is_empty() { ROOT } else { self.lookup(dir)? };
+
+        self.directories[dir].files.iter().find(|f| f.name == name)
+    }
+}
@@ -0,0 +1,66 @@
+/// Visibility from outside and scenic score of every tree
+pub struct Views {
+    pub visible: Vec<Vec<bool>>,
+    pub scenic: Vec<Vec<usize>>
+}
+
+/// Looks along every row and column once in each direction. A stack keeps
+/// the trees which could still block the view of later trees, so every tree
+/// is pushed and popped at most once per direction.
+pub fn analyse(trees: &[Vec<usize>]) -> Views {
+    let height = trees.len();
+    let width = trees.first().map_or(0, |row| row.len());
+    if trees.iter().any(|row| row.len() != width) {
+        panic!("Rows of the forest differ in width");
+    }
+
+    let mut views = Views {
+        visible: vec![vec![false; width]; height],
+        scenic: vec![vec![1; width]; height]
+    };
+
+    for r in 0..height {
+        let line: Vec<(usize, usize)> = (0..width).map(|c| (r, c)).collect();
+        look_along(&line, trees, &mut views);
+        look_along(&line.into_iter().rev().collect::<Vec<_>>(), trees, &mut views);
+    }
+    for c in 0..width {
+        let line: Vec<(usize, usize)> = (0..height).map(|r| (r, c)).collect();
+        look_along(&line, trees, &mut views);
+        look_along(&line.into_iter().rev().collect::<Vec<_>>(), trees, &mut views);
+    }
+
+    views
+}
+
+fn look_along(line: &[(usize, usize)], trees: &[Vec<usize>], views: &mut Views) {
+    // positions on the line with heights which never increase
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+
+    for (i, &(r, c)) in line.iter().enumerate() {
+        let height = trees[r][c];
+        while stack.last().is_some_and(|&(_, h)| h < height) {
+            stack.pop();
+        }
+
+        let distance = match stack.last() {
+            Some(&(blocker, _)) => i - blocker,
+            None => {
+                views.visible[r][c] = true;
+                i
+            }
+        };
+        views.scenic[r][c] *= distance;
+
+        stack.push((i, height));
+    }
+}
+
+pub fn create_tree_map(input: &[&str]) -> Vec<Vec<usize>> {
+    input.iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars()
+            .map(|c| c.to_digit(10).expect("Tree height is not a digit") as usize)
+            .collect())
+        .collect()
+}
@@ -0,0 +1,65 @@
+use std::fs;
+
+use crate::forest::Views;
+
+const PIXELS_PER_TREE: usize = 8;
+
+/// The forest with visible trees in bold green and hidden ones dimmed
+pub fn highlight_visible(trees: &[Vec<usize>], views: &Views) -> String {
+    let mut output = String::new();
+
+    for (r, row) in trees.iter().enumerate() {
+        for (c, height) in row.iter().enumerate() {
+            if views.visible[r][c] {
+                output.push_str(&format!("\x1b[1;32m{}\x1b[0m", height));
+            } else {
+                output.push_str(&format!("\x1b[2m{}\x1b[0m", height));
+            }
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Plain PPM image, the higher the scenic score the brighter the tree
+pub fn write_heatmap(views: &Views, file: &str) {
+    let height = views.scenic.len();
+    let width = views.scenic.first().map_or(0, |row| row.len());
+    let max = views.scenic.iter().flatten().max().copied().unwrap_or(0).max(1);
+
+    let mut image = format!("P3\n{} {}\n255\n", width * PIXELS_PER_TREE, height * PIXELS_PER_TREE);
+    for row in &views.scenic {
+        let pixels: Vec<String> = row.iter()
+            .map(|score| heat(*score as f64 / max as f64))
+            .flat_map(|color| vec![color; PIXELS_PER_TREE])
+            .collect();
+        let line = pixels.join(" ") + "\n";
+
+        for _ in 0..PIXELS_PER_TREE {
+            image.push_str(&line);
+        }
+    }
+
+    fs::write(file, image).expect("Could not write heatmap");
+}
+
+/// Black over red to yellow
+fn heat(t: f64) -> String {
+    let red = (255.0 * (2.0 * t).min(1.0)) as u8;
+    let green = (255.0 * (2.0 * t - 1.0).max(0.0)) as u8;
+
+    format!("{} {} 0", red, green)
+}
+
+/// Row, column and scenic score of the `k` best trees
+pub fn top_scenic(views: &Views, k: usize) -> Vec<(usize, usize, usize)> {
+    let mut scores: Vec<(usize, usize, usize)> = views.scenic.iter()
+        .enumerate()
+        .flat_map(|(r, row)| row.iter().enumerate().map(move |(c, score)| (r, c, *score)))
+        .collect();
+    scores.sort_by(|a, b| b.2.cmp(&a.2).then((a.0, a.1).cmp(&(b.0, b.1))));
+    scores.truncate(k);
+
+    scores
+}
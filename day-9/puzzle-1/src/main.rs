@@ -1,131 +1,80 @@
-use std::collections::HashSet;
+mod render;
+mod rope;
+
 use std::env;
 use std::fs;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Not enough command line arguments");
+    if args.len() < 2 {
+        panic!("Usage: {} <input> [--knots N] [--visited K] [--follow step|snap] [--render] [--frames DIR]", args[0]);
     }
     
     let input = &args[1];
     let lines = fs::read_to_string(input)
         .expect("Could not read file");
     let lines: Vec<&str> = lines.trim().split('\n').collect();
-    let movements = create_movements(&lines);
-    let mut rope = RopeState::new();
-
-    for movement in &movements {
-        rope.apply(movement);
-    }
-
-    println!("Tail moved to {} positions", rope.tail_trace.len());
-}
 
-fn create_movements(lines: &Vec<&str>) -> Vec<RopeMove> {
-    let mut movements: Vec<RopeMove> = Vec::new();
+    let mut knot_count = 2;
+    let mut visited_knot: Option<usize> = None;
+    let mut rule = rope::FollowRule::Step;
+    let mut render = false;
+    let mut frames_dir: Option<String> = None;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        if option == "--render" {
+            render = true;
+            continue;
+        }
 
-    for line in lines {
-        movements.push(RopeMove::from(&line));
+        let value = options.next().expect("Option without value");
+        match option.as_str() {
+            "--knots" => knot_count = value.parse().expect("Knot count is not a number"),
+            "--visited" => visited_knot = Some(value.parse().expect("Knot is not a number")),
+            "--follow" => rule = rope::FollowRule::from(value),
+            "--frames" => frames_dir = Some(value.to_string()),
+            _ => panic!("Unknown option {}", option)
+        }
     }
 
-    movements
-}
-
-#[derive(Debug)]
-enum RopeDirection {
-    Right,
-    Left,
-    Up,
-    Down
-}
-
-impl RopeDirection {
-    fn from(direction: &str) -> Self {
-        let direction = match direction {
-            "R" => RopeDirection::Right,
-            "L" => RopeDirection::Left,
-            "U" => RopeDirection::Up,
-            "D" => RopeDirection::Down,
-            _ => panic!("Unknown direction")
-        };
-
-        direction
+    let movements = create_movements(&lines);
+    let mut rope = rope::Rope::new(knot_count, rule);
+    let mut renderer = if render || frames_dir.is_some() {
+        Some(render::Renderer::new(&movements, frames_dir))
+    } else {
+        None
+    };
+
+    if let Some(renderer) = &mut renderer {
+        renderer.render("Initial State", &rope);
     }
-}
-
-#[derive(Debug)]
-struct RopeMove {
-    direction: RopeDirection,
-    amount: i32
-}
-
-impl RopeMove {
-    fn from(input: &str) -> Self {
-        let mut parts = input.split(" ");
-        let direction = parts.next().expect("Expected direction");
-        let amount = parts.next().expect("Expected amount");
-        let amount: i32 = amount.parse().expect("Amount is no number");
+    for movement in &movements {
+        rope.apply(movement);
 
-        RopeMove { direction: RopeDirection::from(direction), amount }
+        if let Some(renderer) = &mut renderer {
+            renderer.render(&movement.to_string(), &rope);
+        }
     }
-}
 
-#[derive(Debug)]
-struct RopeState {
-    h: (i32, i32),
-    t: (i32, i32),
-    tail_trace: HashSet<(i32, i32)>
-}
+    println!("Tail moved to {} positions", rope.tail_visited().len());
 
-impl RopeState {
-    fn new() -> Self {
-        let mut trace: HashSet<(i32, i32)> = HashSet::new();
-        trace.insert((0, 0)); // tail touches always the starting point
+    if let Some(knot) = visited_knot {
+        if knot >= rope.knot_count() {
+            panic!("Rope has only {} knots", rope.knot_count());
+        }
 
-        RopeState { h: (0, 0), t: (0, 0), tail_trace: trace }
+        let mut cells: Vec<&(i32, i32)> = rope.visited_by(knot).iter().collect();
+        cells.sort();
+        println!("Knot {} visited {} positions: {:?}", knot, cells.len(), cells);
     }
+}
 
-    fn apply(&mut self, movement: &RopeMove) {
-        for _ in 1..=movement.amount {
-            let change = match movement.direction {
-                RopeDirection::Right => (1, 0),
-                RopeDirection::Left => (-1, 0),
-                RopeDirection::Up => (0, 1),
-                RopeDirection::Down => (0, -1)
-            };
-
-            self.h.0 += change.0;
-            self.h.1 += change.1;
+fn create_movements(lines: &[&str]) -> Vec<rope::RopeMove> {
+    let mut movements: Vec<rope::RopeMove> = Vec::new();
 
-            self.update_tail();
-        }
+    for line in lines {
+        movements.push(rope::RopeMove::from(line));
     }
 
-    fn update_tail(&mut self) {
-        let distance_horizontal = self.h.0 - self.t.0;
-        let distance_vertical = self.h.1 - self.t.1;
-
-        let point_distance = ((distance_horizontal.pow(2) + distance_vertical.pow(2)) as f32).sqrt();
-
-        // points are close enough together
-        if point_distance < 2.0 {
-            return;
-        }
-
-        self.t.0 += match distance_horizontal {
-            0 => 0,
-            x if x > 0 => 1,
-            _ => -1
-        };
-        self.t.1 += match distance_vertical {
-            0 => 0,
-            x if x > 0 => 1,
-            _ => -1
-        };
-
-        if !self.tail_trace.contains(&self.t) {
-            self.tail_trace.insert(self.t.clone());
-        }
-    }
+    movements
 }
@@ -0,0 +1,72 @@
+use std::fs;
+
+use crate::rope::{Rope, RopeMove};
+
+/// Draws the rope like the puzzle diagrams: `H` for the head, `1..9` for the
+/// following knots, `s` for the start and `#` for cells the tail visited
+pub struct Renderer {
+    min: (i32, i32),
+    max: (i32, i32),
+    frames_dir: Option<String>,
+    frame: usize
+}
+
+impl Renderer {
+    /// No knot ever leaves the area the head moves in, so the movements
+    /// alone define the size of every frame
+    pub fn new(movements: &[RopeMove], frames_dir: Option<String>) -> Self {
+        let mut head = (0, 0);
+        let mut min = head;
+        let mut max = head;
+
+        for movement in movements {
+            let change = movement.change();
+            head.0 += change.0 * movement.amount();
+            head.1 += change.1 * movement.amount();
+
+            min = (min.0.min(head.0), min.1.min(head.1));
+            max = (max.0.max(head.0), max.1.max(head.1));
+        }
+
+        if let Some(dir) = &frames_dir {
+            fs::create_dir_all(dir).expect("Could not create frames directory");
+        }
+
+        Renderer { min, max, frames_dir, frame: 0 }
+    }
+
+    pub fn render(&mut self, title: &str, rope: &Rope) {
+        let frame = format!("== {} ==\n\n{}", title, self.draw(rope));
+        println!("{}", frame);
+
+        if let Some(dir) = &self.frames_dir {
+            let file = format!("{}/frame-{:05}.txt", dir, self.frame);
+            fs::write(file, frame).expect("Could not write frame");
+        }
+        self.frame += 1;
+    }
+
+    fn draw(&self, rope: &Rope) -> String {
+        let tail = rope.tail_visited();
+        let mut grid = String::new();
+
+        for y in (self.min.1..=self.max.1).rev() {
+            for x in self.min.0..=self.max.0 {
+                let knot = rope.knots().iter().position(|k| *k == (x, y));
+
+                let c = match knot {
+                    Some(0) => 'H',
+                    Some(k) if k < 10 => char::from_digit(k as u32, 10).unwrap(),
+                    Some(_) => '*',
+                    None if (x, y) == (0, 0) => 's',
+                    None if tail.contains(&(x, y)) => '#',
+                    None => '.'
+                };
+                grid.push(c);
+            }
+            grid.push('\n');
+        }
+
+        grid
+    }
+}
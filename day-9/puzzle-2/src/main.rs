@@ -1,145 +1,80 @@
-use std::collections::HashSet;
+mod render;
+mod rope;
+
 use std::env;
 use std::fs;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Not enough command line arguments");
+    if args.len() < 2 {
+        panic!("Usage: {} <input> [--knots N] [--visited K] [--follow step|snap] [--render] [--frames DIR]", args[0]);
     }
     
     let input = &args[1];
     let lines = fs::read_to_string(input)
         .expect("Could not read file");
     let lines: Vec<&str> = lines.trim().split('\n').collect();
-    let movements = create_movements(&lines);
-    let mut rope = RopeState::new();
-
-    for movement in &movements {
-        rope.apply(movement);
-    }
-
-    println!("Tail moved to {} positions", rope.tail_trace.len());
-}
-
-fn create_movements(lines: &Vec<&str>) -> Vec<RopeMove> {
-    let mut movements: Vec<RopeMove> = Vec::new();
-
-    for line in lines {
-        movements.push(RopeMove::from(&line));
-    }
-
-    movements
-}
-
-#[derive(Debug)]
-enum RopeDirection {
-    Right,
-    Left,
-    Up,
-    Down
-}
-
-impl RopeDirection {
-    fn from(direction: &str) -> Self {
-        let direction = match direction {
-            "R" => RopeDirection::Right,
-            "L" => RopeDirection::Left,
-            "U" => RopeDirection::Up,
-            "D" => RopeDirection::Down,
-            _ => panic!("Unknown direction")
-        };
-
-        direction
-    }
-}
-
-#[derive(Debug)]
-struct RopeMove {
-    direction: RopeDirection,
-    amount: i32
-}
 
-impl RopeMove {
-    fn from(input: &str) -> Self {
-        let mut parts = input.split(" ");
-        let direction = parts.next().expect("Expected direction");
-        let amount = parts.next().expect("Expected amount");
-        let amount: i32 = amount.parse().expect("Amount is no number");
+    let mut knot_count = 10;
+    let mut visited_knot: Option<usize> = None;
+    let mut rule = rope::FollowRule::Step;
+    let mut render = false;
+    let mut frames_dir: Option<String> = None;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        if option == "--render" {
+            render = true;
+            continue;
+        }
 
-        RopeMove { direction: RopeDirection::from(direction), amount }
+        let value = options.next().expect("Option without value");
+        match option.as_str() {
+            "--knots" => knot_count = value.parse().expect("Knot count is not a number"),
+            "--visited" => visited_knot = Some(value.parse().expect("Knot is not a number")),
+            "--follow" => rule = rope::FollowRule::from(value),
+            "--frames" => frames_dir = Some(value.to_string()),
+            _ => panic!("Unknown option {}", option)
+        }
     }
-}
-
-#[derive(Debug)]
-struct RopeState {
-    ropes: [(i32, i32); 10],
-    tail_trace: HashSet<(i32, i32)>
-}
 
-impl RopeState {
-    fn new() -> Self {
-        let mut trace: HashSet<(i32, i32)> = HashSet::new();
-        trace.insert((0, 0)); // tail touches always the starting point
-
-        let start_positions: [(i32, i32); 10] = [(0, 0); 10];
-        RopeState { ropes: start_positions, tail_trace: trace }
+    let movements = create_movements(&lines);
+    let mut rope = rope::Rope::new(knot_count, rule);
+    let mut renderer = if render || frames_dir.is_some() {
+        Some(render::Renderer::new(&movements, frames_dir))
+    } else {
+        None
+    };
+
+    if let Some(renderer) = &mut renderer {
+        renderer.render("Initial State", &rope);
     }
+    for movement in &movements {
+        rope.apply(movement);
 
-    fn apply(&mut self, movement: &RopeMove) {
-        for _ in 1..=movement.amount {
-            let change = match movement.direction {
-                RopeDirection::Right => (1, 0),
-                RopeDirection::Left => (-1, 0),
-                RopeDirection::Up => (0, 1),
-                RopeDirection::Down => (0, -1)
-            };
-
-            // update head
-            let mut rope = self.ropes[0];
-            rope.0 += change.0;
-            rope.1 += change.1;
-            self.ropes[0] = rope;
-
-            for t_id in 1..self.ropes.len() {
-                self.update_tail(t_id);
-            }
+        if let Some(renderer) = &mut renderer {
+            renderer.render(&movement.to_string(), &rope);
         }
     }
 
-    fn update_tail(&mut self, tail_id: usize) {
-        let mut tail = self.ropes[tail_id];
-        let successor_tail = self.ropes[tail_id - 1];
-
-        let distance_horizontal = successor_tail.0 - tail.0;
-        let distance_vertical = successor_tail.1 - tail.1;
-
-        let point_distance = ((distance_horizontal.pow(2) + distance_vertical.pow(2)) as f32).sqrt();
+    println!("Tail moved to {} positions", rope.tail_visited().len());
 
-        // points are close enough together
-        if point_distance < 2.0 {
-            return;
+    if let Some(knot) = visited_knot {
+        if knot >= rope.knot_count() {
+            panic!("Rope has only {} knots", rope.knot_count());
         }
 
-        tail.0 += match distance_horizontal {
-            0 => 0,
-            x if x > 0 => 1,
-            _ => -1
-        };
-        tail.1 += match distance_vertical {
-            0 => 0,
-            x if x > 0 => 1,
-            _ => -1
-        };
+        let mut cells: Vec<&(i32, i32)> = rope.visited_by(knot).iter().collect();
+        cells.sort();
+        println!("Knot {} visited {} positions: {:?}", knot, cells.len(), cells);
+    }
+}
 
-        self.ropes[tail_id] = tail;
+fn create_movements(lines: &[&str]) -> Vec<rope::RopeMove> {
+    let mut movements: Vec<rope::RopeMove> = Vec::new();
 
-        if tail_id < self.ropes.len() - 1 {
-            return;
-        }
-
-        if !self.tail_trace.contains(&tail) {
-            self.tail_trace.insert(tail);
-        }
+    for line in lines {
+        movements.push(rope::RopeMove::from(line));
     }
+
+    movements
 }
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum RopeDirection {
+    Right,
+    Left,
+    Up,
+    Down,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight
+}
+
+impl RopeDirection {
+    fn from(direction: &str) -> Self {
+        match direction {
+            "R" => RopeDirection::Right,
+            "L" => RopeDirection::Left,
+            "U" => RopeDirection::Up,
+            "D" => RopeDirection::Down,
+            "UL" => RopeDirection::UpLeft,
+            "UR" => RopeDirection::UpRight,
+            "DL" => RopeDirection::DownLeft,
+            "DR" => RopeDirection::DownRight,
+            _ => panic!("Unknown direction")
+        }
+    }
+
+    fn change(&self) -> (i32, i32) {
+        match self {
+            RopeDirection::Right => (1, 0),
+            RopeDirection::Left => (-1, 0),
+            RopeDirection::Up => (0, 1),
+            RopeDirection::Down => (0, -1),
+            RopeDirection::UpLeft => (-1, 1),
+            RopeDirection::UpRight => (1, 1),
+            RopeDirection::DownLeft => (-1, -1),
+            RopeDirection::DownRight => (1, -1)
+        }
+    }
+}
+
+impl fmt::Display for RopeDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = match self {
+            RopeDirection::Right => "R",
+            RopeDirection::Left => "L",
+            RopeDirection::Up => "U",
+            RopeDirection::Down => "D",
+            RopeDirection::UpLeft => "UL",
+            RopeDirection::UpRight => "UR",
+            RopeDirection::DownLeft => "DL",
+            RopeDirection::DownRight => "DR"
+        };
+
+        write!(f, "{}", direction)
+    }
+}
+
+/// How a knot catches up once its predecessor is no longer adjacent
+#[derive(Debug)]
+pub enum FollowRule {
+    /// Move one step towards the predecessor, diagonally if needed
+    Step,
+    /// Jump onto the cell the predecessor just left
+    Snap
+}
+
+impl FollowRule {
+    pub fn from(rule: &str) -> Self {
+        match rule {
+            "step" => FollowRule::Step,
+            "snap" => FollowRule::Snap,
+            _ => panic!("Unknown follow rule {}", rule)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RopeMove {
+    direction: RopeDirection,
+    amount: i32
+}
+
+impl RopeMove {
+    pub fn from(input: &str) -> Self {
+        let mut parts = input.split(' ');
+        let direction = parts.next().expect("Expected direction");
+        let amount = parts.next().expect("Expected amount");
+        let amount: i32 = amount.parse().expect("Amount is no number");
+
+        RopeMove { direction: RopeDirection::from(direction), amount }
+    }
+
+    pub fn change(&self) -> (i32, i32) {
+        self.direction.change()
+    }
+
+    pub fn amount(&self) -> i32 {
+        self.amount
+    }
+}
+
+impl fmt::Display for RopeMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.direction, self.amount)
+    }
+}
+
+/// A rope of any number of knots. The first knot is the head, every further
+/// knot follows its predecessor.
+#[derive(Debug)]
+pub struct Rope {
+    knots: Vec<(i32, i32)>,
+    visited: Vec<HashSet<(i32, i32)>>,
+    rule: FollowRule
+}
+
+impl Rope {
+    pub fn new(knot_count: usize, rule: FollowRule) -> Self {
+        if knot_count == 0 {
+            panic!("A rope needs at least one knot");
+        }
+
+        // all knots touch the starting point
+        let visited = vec![HashSet::from([(0, 0)]); knot_count];
+        Rope { knots: vec![(0, 0); knot_count], visited, rule }
+    }
+
+    pub fn apply(&mut self, movement: &RopeMove) {
+        let change = movement.direction.change();
+
+        for _ in 1..=movement.amount {
+            let previous = self.knots.clone();
+
+            // update head
+            self.knots[0].0 += change.0;
+            self.knots[0].1 += change.1;
+            self.visited[0].insert(self.knots[0]);
+
+            for knot_id in 1..self.knots.len() {
+                self.follow(knot_id, previous[knot_id - 1]);
+            }
+        }
+    }
+
+    fn follow(&mut self, knot_id: usize, predecessor_left: (i32, i32)) {
+        let mut knot = self.knots[knot_id];
+        let predecessor = self.knots[knot_id - 1];
+
+        let distance_horizontal = predecessor.0 - knot.0;
+        let distance_vertical = predecessor.1 - knot.1;
+
+        // points touch, also diagonally (Chebyshev distance)
+        if distance_horizontal.abs().max(distance_vertical.abs()) <= 1 {
+            return;
+        }
+
+        match self.rule {
+            FollowRule::Step => {
+                knot.0 += distance_horizontal.signum();
+                knot.1 += distance_vertical.signum();
+            },
+            FollowRule::Snap => knot = predecessor_left
+        }
+
+        self.knots[knot_id] = knot;
+        self.visited[knot_id].insert(knot);
+    }
+
+    pub fn knots(&self) -> &[(i32, i32)] {
+        &self.knots
+    }
+
+    pub fn knot_count(&self) -> usize {
+        self.knots.len()
+    }
+
+    /// All cells knot `knot_id` has been on, the head is knot 0
+    pub fn visited_by(&self, knot_id: usize) -> &HashSet<(i32, i32)> {
+        self.visited.get(knot_id).expect("Unknown knot")
+    }
+
+    pub fn tail_visited(&self) -> &HashSet<(i32, i32)> {
+        self.visited_by(self.knots.len() - 1)
+    }
+}